@@ -1,6 +1,7 @@
-use std::process::exit;
+use intcode::symbolic::Symbolic;
+use intcode::Bit;
 
-fn run(v: &mut Vec<usize>) {
+fn run(v: &mut [usize]) {
     for i in (0..v.len()).step_by(4) {
         let op = v[i];
         if op == 99 {
@@ -51,18 +52,30 @@ fn main() {
     //    run(&mut nums);
     //    println!("{}", nums[0]);
 
-    for noun in 0..=99 {
-        for verb in 0..=99 {
-            if compute(&nums, noun, verb) == 19_690_720 {
-                println!("{}", 100 * noun + verb);
-                exit(0)
-            }
-        }
-    }
+    // mem[0] comes out as a linear function of the noun and verb so just solve for them
+    let mut sym = Symbolic::new(&nums.iter().map(|n| *n as Bit).collect::<Vec<_>>());
+    sym.unknown(1, "noun");
+    sym.unknown(2, "verb");
+    sym.run().expect("The program failed");
+
+    let nv = sym
+        .solve(
+            &sym.mem()[0],
+            19_690_720,
+            &[("noun", 0..=99), ("verb", 0..=99)],
+        )
+        .expect("No noun and verb give 19690720");
+
+    let (noun, verb) = (nv["noun"] as usize, nv["verb"] as usize);
+    assert_eq!(compute(&nums, noun, verb), 19_690_720);
+    println!("{}", 100 * noun + verb);
 }
 
 #[cfg(test)]
 mod test {
+    use intcode::computer::Cmd;
+    use intcode::fuzz::{backends, fuzz, Backend, Gen};
+
     fn test(mut v1: Vec<usize>, v2: Vec<usize>) {
         super::run(&mut v1);
         assert_eq!(v1, v2);
@@ -86,4 +99,32 @@ mod test {
             vec![30, 1, 1, 4, 2, 5, 6, 0, 99],
         );
     }
+
+    #[test]
+    fn matches_intcode() {
+        // Only what this interpreter understands, and it panics on anything it can't run
+        let day_02 = Backend {
+            name: "day_02",
+            run: |case| {
+                let mut v: Vec<usize> = case.mem.iter().map(|b| *b as usize).collect();
+                std::panic::catch_unwind(move || {
+                    super::run(&mut v);
+                    (v.into_iter().map(|n| n as intcode::Bit).collect(), vec![])
+                })
+                .map_err(|_| "panicked".to_owned())
+            },
+        };
+
+        let gen = Gen {
+            cmds: vec![Cmd::Add, Cmd::Multiply],
+            all_modes: false,
+            self_modify: false,
+            values: 0..=9,
+            ..Gen::default()
+        };
+
+        if let Some(m) = fuzz(&gen, 2, 500, &[backends()[0], day_02]) {
+            panic!("day_02 disagrees with intcode\n{}", m);
+        }
+    }
 }
@@ -90,7 +90,7 @@ impl Inst {
             *steps += 1;
 
             if first {
-                set.entry(pos.clone()).or_insert(*steps);
+                set.entry(*pos).or_insert(*steps);
             } else if let Some(o_dist) = set.get(pos) {
                 let dist = *steps + *o_dist;
                 let old = lowest.get_or_insert(dist);
@@ -1,10 +1,10 @@
-use crossbeam::scope;
-use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::rc::Rc;
 use std::time::Instant;
 
-use bus::Bus;
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures::executor::block_on;
+use futures::StreamExt;
+use intcode::async_io::async_pair;
 use intcode::computer::Computer;
 use intcode::error::Result;
 use intcode::Bit;
@@ -17,8 +17,7 @@ fn sum1(mem: &[Bit], a: Bit, b: Bit, c: Bit, d: Bit, e: Bit) -> Result<Bit> {
     let (mut i5, mut o4) = intcode::chan_pair(&[e]);
 
     let mut start = VecDeque::from(vec![a, 0]);
-    let end = Rc::new(RefCell::new(vec![]));
-    let mut o5 = end.clone();
+    let mut o5 = vec![];
 
     let mut c1 = Computer::new(mem.to_owned(), &mut start, &mut o1);
     let mut c2 = Computer::new(mem.to_owned(), &mut i2, &mut o2);
@@ -31,12 +30,12 @@ fn sum1(mem: &[Bit], a: Bit, b: Bit, c: Bit, d: Bit, e: Bit) -> Result<Bit> {
     c3.run()?;
     c4.run()?;
     c5.run()?;
+    drop(c5);
 
-    let v = end.borrow();
-    if v.len() != 1 {
+    if o5.len() != 1 {
         panic!("Invalid output len");
     }
-    Ok(v[0])
+    Ok(o5[0])
 }
 
 #[allow(unused)]
@@ -88,50 +87,52 @@ fn part1() {
     println!("{}", max);
 }
 
+/// Every amplifier is a task on this thread, with the last one fed back into the first
 #[allow(clippy::many_single_char_names)]
 fn sum2(mem: &[Bit], a: Bit, b: Bit, c: Bit, d: Bit, e: Bit) -> Result<Bit> {
-    let mut bus = Bus::new(5);
-    let mut start = bus.add_rx();
-    bus.broadcast(a);
-    bus.broadcast(0);
-
-    let mut outs = bus.add_rx();
-
-    let (mut i2, mut o1) = intcode::chan_pair(&[b]);
-    let (mut i3, mut o2) = intcode::chan_pair(&[c]);
-    let (mut i4, mut o3) = intcode::chan_pair(&[d]);
-    let (mut i5, mut o4) = intcode::chan_pair(&[e]);
-
-    scope(|s| {
-        s.spawn(move |_| {
-            Computer::new(mem.to_owned(), &mut start, &mut o1)
-                .run()
-                .unwrap()
-        });
-        s.spawn(move |_| {
-            Computer::new(mem.to_owned(), &mut i2, &mut o2)
-                .run()
-                .unwrap()
-        });
-        s.spawn(move |_| {
-            Computer::new(mem.to_owned(), &mut i3, &mut o3)
-                .run()
-                .unwrap()
-        });
-        s.spawn(move |_| {
-            Computer::new(mem.to_owned(), &mut i4, &mut o4)
-                .run()
-                .unwrap()
-        });
-        s.spawn(move |_| {
-            Computer::new(mem.to_owned(), &mut i5, &mut bus)
-                .run()
-                .unwrap()
-        });
-
-        Ok(outs.iter().last().unwrap())
-    })
-    .unwrap()
+    let (i1, feed) = async_pair(&[a, 0]);
+    let (i2, o1) = async_pair(&[b]);
+    let (i3, o2) = async_pair(&[c]);
+    let (i4, o3) = async_pair(&[d]);
+    let (i5, o4) = async_pair(&[e]);
+    let (mut back, o5) = async_pair(&[]);
+
+    let amp = |mut cin: UnboundedReceiver<Bit>, mut cout: UnboundedSender<Bit>| {
+        let mem = mem.to_owned();
+        async move {
+            let mut comp = Computer::without_io(mem);
+            comp.run_async(&mut cin, &mut cout).await.map(|_| ())
+        }
+    };
+
+    // Loops the last amplifier back around to the first, keeping hold of what went by last
+    let feedback = async move {
+        let mut last = None;
+        while let Some(v) = back.next().await {
+            last = Some(v);
+            // The first amplifier halting first is fine, only the last value matters
+            let _ = feed.unbounded_send(v);
+        }
+        last
+    };
+
+    let (r1, r2, r3, r4, r5, last) = block_on(async {
+        futures::join!(
+            amp(i1, o1),
+            amp(i2, o2),
+            amp(i3, o3),
+            amp(i4, o4),
+            amp(i5, o5),
+            feedback
+        )
+    });
+    r1?;
+    r2?;
+    r3?;
+    r4?;
+    r5?;
+
+    Ok(last.expect("The amplifiers never output anything"))
 }
 
 fn part2() {
@@ -1,8 +1,6 @@
-use std::collections::VecDeque;
 use std::time::Instant;
 
 use intcode::computer::Computer;
-use intcode::output::PrintOutput;
 use intcode::Bit;
 
 fn run(ins: Bit) {
@@ -12,12 +10,9 @@ fn run(ins: Bit) {
         panic!("Invalid computer mem")
     }
 
-    let mut cin = VecDeque::from(vec![ins]);
-    let mut cout = PrintOutput;
-
-    Computer::new(mem.into_iter().next().unwrap(), &mut cin, &mut cout)
-        .run()
-        .unwrap();
+    for out in Computer::outputs(mem.into_iter().next().unwrap(), vec![ins]) {
+        println!("{}", out.unwrap());
+    }
 }
 
 #[allow(unused)]
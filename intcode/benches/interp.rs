@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use intcode::computer::{Computer, DecodeCache, RunState};
+use intcode::Bit;
+
+fn program(day: &str) -> Vec<Bit> {
+    let path = format!("{}/../{}/input", env!("CARGO_MANIFEST_DIR"), day);
+    Computer::get_bits(path).unwrap().remove(0)
+}
+
+fn run(mem: &[Bit], input: &[Bit], cache: Option<&DecodeCache>) -> Vec<Bit> {
+    let mut cin: VecDeque<Bit> = input.iter().copied().collect();
+    let mut cout = vec![];
+
+    let mut c = Computer::new(mem.to_vec(), &mut cin, &mut cout);
+    c.set_cache(cache.cloned());
+    c.run().unwrap();
+
+    drop(c);
+    cout
+}
+
+fn permutations(items: &[Bit]) -> Vec<Vec<Bit>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+
+    let mut perms = vec![];
+    for (i, first) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(i);
+        for mut p in permutations(&rest) {
+            p.insert(0, *first);
+            perms.push(p);
+        }
+    }
+    perms
+}
+
+/// The day 7 part 1 search: every phase order through a chain of five amps
+fn amp_search(mem: &[Bit], cache: Option<&DecodeCache>) -> Bit {
+    let mut cin = VecDeque::new();
+    let mut cout = vec![];
+
+    permutations(&[0, 1, 2, 3, 4])
+        .iter()
+        .map(|phases| {
+            phases.iter().fold(0, |signal, phase| {
+                let mut c = Computer::new(mem.to_vec(), &mut cin, &mut cout);
+                c.set_cache(cache.cloned());
+                c.feed(*phase);
+                c.feed(signal);
+                match c.resume().unwrap() {
+                    RunState::Output(o) => o,
+                    other => panic!("Expected an output, got {:?}", other),
+                }
+            })
+        })
+        .max()
+        .unwrap()
+}
+
+/// Each program runs as is and again sharing one cache decoded ahead of time, the way a search
+/// would
+fn interp(c: &mut Criterion) {
+    let day_05 = program("day_05");
+    let day_07 = program("day_07");
+    let day_09 = program("day_09");
+
+    let caches = [
+        ("plain", None, None, None),
+        (
+            "cached",
+            Some(DecodeCache::new(&day_05)),
+            Some(DecodeCache::new(&day_07)),
+            Some(DecodeCache::new(&day_09)),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("interp");
+    for (name, c05, c07, _) in &caches {
+        group.bench_function(BenchmarkId::new("day_05", name), |b| {
+            b.iter(|| run(&day_05, &[5], c05.as_ref()))
+        });
+        group.bench_function(BenchmarkId::new("day_07", name), |b| {
+            b.iter(|| amp_search(&day_07, c07.as_ref()))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("interp_long");
+    group.sample_size(10);
+    for (name, _, _, c09) in &caches {
+        group.bench_function(BenchmarkId::new("day_09", name), |b| {
+            b.iter(|| run(&day_09, &[2], c09.as_ref()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, interp);
+criterion_main!(benches);
@@ -0,0 +1,387 @@
+//! A small assembly language for intcode.
+//!
+//! ```text
+//! ; comments run to the end of the line
+//! start:  input     [value]            ; position mode
+//!         multiply  [value], #2, rel+0 ; immediate and relative modes
+//!         output    rel+0
+//!         jumptrue  #1, #start         ; labels can be used anywhere a number can
+//! value:  data      0
+//!         zero      3                  ; three zeros
+//! ```
+//!
+//! Mnemonics are the lower cased [`Cmd`] names and operands are written the same way
+//! [`Operand`](crate::disasm::Operand) displays them, so any listing from
+//! [`disasm::listing`](crate::disasm::listing) assembles back to the memory it came from.
+
+use std::collections::HashMap;
+
+use crate::computer::{Cmd, Mode};
+use crate::disasm::encode;
+use crate::error::CompError::AsmErr;
+use crate::error::Result;
+use crate::Bit;
+
+/// The most words a single `zero` can reserve
+const MAX_ZERO: Bit = 1 << 20;
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(Bit),
+    Label(String, Bit, usize),
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+    Ins(Cmd, Vec<(Mode, Expr)>),
+    Data(Vec<Expr>),
+}
+
+/// A piece of a line along with the 1 based column it starts at
+#[derive(Debug, Copy, Clone)]
+struct Span<'s>(&'s str, usize);
+
+impl<'s> Span<'s> {
+    fn trim(self) -> Self {
+        let start = self.0.len() - self.0.trim_start().len();
+        Span(self.0.trim(), self.1 + start)
+    }
+
+    fn split_at(self, i: usize) -> (Self, Self) {
+        (Span(&self.0[..i], self.1), Span(&self.0[i..], self.1 + i))
+    }
+
+    fn split(self, sep: char) -> Vec<Self> {
+        let mut parts = vec![];
+        let mut rest = self;
+        while let Some(i) = rest.0.find(sep) {
+            let (part, tail) = rest.split_at(i);
+            parts.push(part.trim());
+            rest = tail.split_at(1).1;
+        }
+        parts.push(rest.trim());
+        parts
+    }
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+struct Parser {
+    line: usize,
+}
+
+impl Parser {
+    fn err<T>(&self, col: usize, msg: String) -> Result<T> {
+        Err(AsmErr(self.line, col, msg))
+    }
+
+    fn num(&self, s: Span) -> Result<Bit> {
+        s.0.parse()
+            .or_else(|_| self.err(s.1, format!("Invalid number: {}", s.0)))
+    }
+
+    fn expr(&self, s: Span) -> Result<Expr> {
+        let s = s.trim();
+        if s.0.is_empty() {
+            return self.err(s.1, "Missing a value".to_owned());
+        }
+
+        if let Ok(n) = s.0.parse() {
+            return Ok(Expr::Num(n));
+        }
+
+        // label, label+n or label-n
+        let (name, offset) = match s.0.find(['+', '-']) {
+            Some(i) => {
+                let (name, off) = s.split_at(i);
+                (name.trim(), self.num(off.trim())?)
+            }
+            None => (s, 0),
+        };
+
+        if is_ident(name.0) {
+            Ok(Expr::Label(name.0.to_owned(), offset, name.1))
+        } else {
+            self.err(s.1, format!("Invalid value: {}", s.0))
+        }
+    }
+
+    fn operand(&self, s: Span) -> Result<(Mode, Expr)> {
+        let s = s.trim();
+        let text = s.0;
+
+        if text.starts_with('[') {
+            if !text.ends_with(']') {
+                return self.err(s.1 + text.len(), "Missing the closing ]".to_owned());
+            }
+            let inner = Span(&text[1..text.len() - 1], s.1 + 1);
+            return Ok((Mode::Position, self.expr(inner)?));
+        }
+
+        if let Some(imm) = text.strip_prefix('#') {
+            return Ok((Mode::Immediate, self.expr(Span(imm, s.1 + 1))?));
+        }
+
+        if let Some(off) = text.strip_prefix("rel") {
+            let off = Span(off, s.1 + 3).trim();
+            if off.0.is_empty() {
+                return Ok((Mode::Relative, Expr::Num(0)));
+            }
+            if off.0.starts_with('+') || off.0.starts_with('-') {
+                return Ok((Mode::Relative, Expr::Num(self.num(off)?)));
+            }
+        }
+
+        self.err(
+            s.1,
+            format!(
+                "Invalid operand {:?}; use [addr], #value or rel+offset",
+                text
+            ),
+        )
+    }
+
+    /// Parses a line into its label (if any) and item (if any)
+    fn line<'s>(&self, text: &'s str) -> Result<(Option<Span<'s>>, Option<Item>)> {
+        let text = match text.find(';') {
+            Some(i) => &text[..i],
+            None => text,
+        };
+        let mut rest = Span(text, 1).trim();
+
+        let mut label = None;
+        if let Some(i) = rest.0.find(':') {
+            let (name, tail) = rest.split_at(i);
+            let name = name.trim();
+            if !is_ident(name.0) {
+                return self.err(name.1, format!("Invalid label: {}", name.0));
+            }
+            label = Some(name);
+            rest = tail.split_at(1).1.trim();
+        }
+
+        if rest.0.is_empty() {
+            return Ok((label, None));
+        }
+
+        let (word, args) = match rest.0.find(char::is_whitespace) {
+            Some(i) => rest.split_at(i),
+            None => rest.split_at(rest.0.len()),
+        };
+        let args = args.trim();
+        let args = if args.0.is_empty() {
+            vec![]
+        } else {
+            args.split(',')
+        };
+
+        let item = match word.0.to_ascii_lowercase().as_str() {
+            "data" => {
+                if args.is_empty() {
+                    return self.err(word.1, "data needs at least one value".to_owned());
+                }
+                Item::Data(
+                    args.into_iter()
+                        .map(|a| self.expr(a))
+                        .collect::<Result<_>>()?,
+                )
+            }
+
+            "zero" => match args.as_slice() {
+                [n] => {
+                    let n = self.num(*n)?;
+                    if n < 0 {
+                        return self.err(args[0].1, "zero needs a positive count".to_owned());
+                    }
+                    if n > MAX_ZERO {
+                        return self.err(
+                            args[0].1,
+                            format!("zero can't reserve more than {} words", MAX_ZERO),
+                        );
+                    }
+                    Item::Data(vec![Expr::Num(0); n as usize])
+                }
+                _ => return self.err(word.1, "zero takes exactly one count".to_owned()),
+            },
+
+            // Cmd's FromStr also takes opcode numbers which would be confusing here
+            w if w.parse::<Bit>().is_err() => {
+                let cmd = match w.parse::<Cmd>() {
+                    Ok(c) => c,
+                    Err(e) => return self.err(word.1, e),
+                };
+                if args.len() != cmd.arity() {
+                    return self.err(
+                        word.1,
+                        format!(
+                            "{} takes {} operands but got {}",
+                            cmd.mnemonic(),
+                            cmd.arity(),
+                            args.len()
+                        ),
+                    );
+                }
+                let mut ops = Vec::with_capacity(args.len());
+                for (i, a) in args.into_iter().enumerate() {
+                    let op = self.operand(a)?;
+                    if op.0 == Mode::Immediate && cmd.writes(i) {
+                        return self.err(
+                            a.trim().1,
+                            format!("{} can't write to an immediate value", cmd.mnemonic()),
+                        );
+                    }
+                    ops.push(op);
+                }
+                Item::Ins(cmd, ops)
+            }
+
+            _ => return self.err(word.1, format!("Unknown mnemonic: {}", word.0)),
+        };
+
+        Ok((label, Some(item)))
+    }
+}
+
+pub fn assemble(src: &str) -> Result<Vec<Bit>> {
+    let mut labels = HashMap::new();
+    let mut items = vec![];
+    let mut addr = 0;
+
+    for (lnum, text) in src.lines().enumerate() {
+        let p = Parser { line: lnum + 1 };
+        let (label, item) = p.line(text)?;
+
+        if let Some(l) = label {
+            if labels.insert(l.0.to_owned(), addr).is_some() {
+                return p.err(l.1, format!("Duplicate label: {}", l.0));
+            }
+        }
+
+        if let Some(item) = item {
+            addr += match &item {
+                Item::Ins(cmd, _) => 1 + cmd.arity(),
+                Item::Data(vals) => vals.len(),
+            };
+            items.push((lnum + 1, item));
+        }
+    }
+
+    let mut mem = Vec::with_capacity(addr);
+    for (line, item) in items {
+        let resolve = |e: &Expr| match e {
+            Expr::Num(n) => Ok(*n),
+            Expr::Label(name, off, col) => labels
+                .get(name)
+                .ok_or_else(|| AsmErr(line, *col, format!("Unknown label: {}", name)))
+                .and_then(|a| {
+                    (*a as Bit)
+                        .checked_add(*off)
+                        .ok_or_else(|| AsmErr(line, *col, "label offset out of range".to_owned()))
+                }),
+        };
+
+        match item {
+            Item::Ins(cmd, ops) => {
+                let modes: Vec<Mode> = ops.iter().map(|o| o.0).collect();
+                mem.push(Bit::from(encode(cmd, &modes)));
+                for (_, e) in &ops {
+                    mem.push(resolve(e)?);
+                }
+            }
+
+            Item::Data(vals) => {
+                for e in &vals {
+                    mem.push(resolve(e)?);
+                }
+            }
+        }
+    }
+
+    Ok(mem)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::computer::Computer;
+    use crate::disasm::listing;
+
+    fn err(src: &str) -> (usize, usize) {
+        match assemble(src) {
+            Err(AsmErr(line, col, _)) => (line, col),
+            other => panic!("Expected an asm error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn quine() {
+        let src = "
+            ; the day 9 example that outputs itself
+            start:  adjustrel #1
+                    output    rel-1
+                    add       [count], #1, [count]
+                    equals    [count], #16, [done]
+                    jumpfalse [done], #start
+                    halt
+            count:  data      0
+            done:   data      0
+        ";
+
+        // The original keeps count/done past the end of memory, here they're declared instead
+        let mem = assemble(src).unwrap();
+        assert_eq!(
+            mem,
+            vec![109, 1, 204, -1, 1001, 16, 1, 16, 1008, 16, 16, 17, 1006, 17, 0, 99, 0, 0]
+        );
+
+        let mut cin = std::collections::VecDeque::new();
+        let mut cout = vec![];
+        Computer::new(mem.clone(), &mut cin, &mut cout)
+            .run()
+            .unwrap();
+        assert_eq!(cout, &mem[..16]);
+    }
+
+    #[test]
+    fn labels_and_directives() {
+        let src = "a: data b, b+1, a-2\nzero 2\nb: halt";
+        assert_eq!(assemble(src).unwrap(), vec![5, 6, -2, 0, 0, 99]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(err("halt\n  bogus #1"), (2, 3));
+        assert_eq!(err("output  [1"), (1, 11));
+        assert_eq!(err("add #1, #2"), (1, 1));
+        assert_eq!(err("output #missing"), (1, 9));
+        assert_eq!(err("input 12"), (1, 7));
+        assert_eq!(err("x: halt\nx: halt"), (2, 1));
+        assert_eq!(err("zero 9999999999"), (1, 6));
+        assert_eq!(err("input #5"), (1, 7));
+        assert_eq!(err("add #1, #2,  #3"), (1, 14));
+        assert_eq!(err("halt\nequals [1], #2, #3"), (2, 17));
+        assert_eq!(err("halt\nb: data b+9223372036854775807"), (2, 9));
+    }
+
+    #[test]
+    fn round_trip() {
+        for day in &["day_02", "day_05", "day_07", "day_09"] {
+            let path = format!("{}/../{}/input", env!("CARGO_MANIFEST_DIR"), day);
+            for mem in Computer::get_bits(path).unwrap() {
+                assert_eq!(assemble(&listing(&mem)).unwrap(), mem, "{}", day);
+            }
+        }
+
+        // Decodes as an add that writes to an immediate, which only works as data
+        let mem = vec![11101, 1, 2, 3, 99];
+        assert_eq!(assemble(&listing(&mem)).unwrap(), mem);
+    }
+}
@@ -0,0 +1,188 @@
+//! Non-blocking counterparts of [`Input`] and [`Output`] for [`Computer::run_async`], so lots of
+//! computers can run as tasks on one executor instead of costing a thread each.
+//!
+//! Computers talk to each other over `futures` channels; [`from_input`] and [`into_output`] hook
+//! up anything blocking, like the crossbeam channels, at the cost of one thread each.
+//!
+//! [`Computer::run_async`]: crate::computer::Computer::run_async
+
+use std::task::{Context, Poll};
+use std::thread;
+
+use futures::channel::mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender};
+use futures::executor::block_on;
+use futures::StreamExt;
+
+use crate::error::CompError::{InputErrStr, OutputErr};
+use crate::error::Result;
+use crate::input::Input;
+use crate::output::Output;
+use crate::word::Word;
+use crate::Bit;
+
+pub trait AsyncInput<W = Bit> {
+    fn poll_in(&mut self, cx: &mut Context<'_>) -> Poll<Result<W, W>>;
+}
+
+pub trait AsyncOutput<W = Bit> {
+    /// Only takes the value once it returns ready; until then it'll be offered again
+    fn poll_out(&mut self, cx: &mut Context<'_>, n: &W) -> Poll<Result<(), W>>;
+}
+
+fn closed<W>(v: Option<W>) -> Result<W, W> {
+    v.ok_or(InputErrStr("The channel was closed"))
+}
+
+impl<W: Word> AsyncInput<W> for UnboundedReceiver<W> {
+    fn poll_in(&mut self, cx: &mut Context<'_>) -> Poll<Result<W, W>> {
+        self.poll_next_unpin(cx).map(closed)
+    }
+}
+
+impl<W: Word> AsyncInput<W> for Receiver<W> {
+    fn poll_in(&mut self, cx: &mut Context<'_>) -> Poll<Result<W, W>> {
+        self.poll_next_unpin(cx).map(closed)
+    }
+}
+
+impl<W: Word + Send + 'static> AsyncOutput<W> for UnboundedSender<W> {
+    fn poll_out(&mut self, _cx: &mut Context<'_>, n: &W) -> Poll<Result<(), W>> {
+        Poll::Ready(
+            self.unbounded_send(n.clone())
+                .map_err(|e| OutputErr(Box::new(e.into_send_error()))),
+        )
+    }
+}
+
+impl<W: Word + Send + 'static> AsyncOutput<W> for Sender<W> {
+    fn poll_out(&mut self, cx: &mut Context<'_>, n: &W) -> Poll<Result<(), W>> {
+        let ready = self.poll_ready(cx).map_err(|e| OutputErr(Box::new(e)));
+        ready.map(|r| {
+            r.and_then(|_| {
+                self.start_send(n.clone())
+                    .map_err(|e| OutputErr(Box::new(e)))
+            })
+        })
+    }
+}
+
+/// The async version of [`chan_pair`](crate::chan_pair)
+pub fn async_pair<W: Word>(start_ins: &[W]) -> (UnboundedReceiver<W>, UnboundedSender<W>) {
+    let (send, recv) = mpsc::unbounded();
+    for i in start_ins {
+        send.unbounded_send(i.clone()).unwrap();
+    }
+
+    (recv, send)
+}
+
+/// Pulls values out of a blocking input on a thread of its own until it fails or nobody's
+/// listening anymore
+pub fn from_input<W, I>(mut input: I) -> UnboundedReceiver<W>
+where
+    W: Word + Send + 'static,
+    I: Input<W> + Send + 'static,
+{
+    let (send, recv) = mpsc::unbounded();
+    thread::spawn(move || {
+        while let Ok(v) = input.get_in() {
+            if send.unbounded_send(v).is_err() {
+                break;
+            }
+        }
+    });
+    recv
+}
+
+/// Passes values on to a blocking output on a thread of its own until every sender is gone
+pub fn into_output<W, O>(mut output: O) -> UnboundedSender<W>
+where
+    W: Word + Send + 'static,
+    O: Output<W> + Send + 'static,
+{
+    let (send, mut recv) = mpsc::unbounded();
+    thread::spawn(move || {
+        while let Some(v) = block_on(recv.next()) {
+            if output.put_out(v).is_err() {
+                break;
+            }
+        }
+    });
+    send
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::computer::{Computer, RunState};
+    use crate::watch::{Access, Watch};
+    use futures::executor::LocalPool;
+    use futures::task::LocalSpawnExt;
+
+    #[test]
+    fn chained_tasks() {
+        const MACHINES: usize = 200;
+
+        // Outputs its input plus one
+        let mem = vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+
+        let (first, start) = crate::chan_pair(&[0, 100]);
+        let (last, end) = crossbeam::unbounded();
+
+        let mut pool = LocalPool::new();
+        let mut cin = from_input(first);
+        for i in 0..MACHINES {
+            let (next, mut cout) = async_pair(&[]);
+            if i == MACHINES - 1 {
+                cout = into_output(last.clone());
+            }
+            let mem = mem.clone();
+
+            pool.spawner()
+                .spawn_local(async move {
+                    let mut comp = Computer::without_io(mem);
+                    let stop = comp.run_async(&mut cin, &mut cout).await.unwrap();
+                    assert_eq!((stop, comp.steps()), (RunState::Halted, 4));
+                })
+                .unwrap();
+            cin = next;
+        }
+        drop(last);
+        pool.run();
+        drop(start);
+
+        // Only the first value makes it through, every machine halts after one
+        assert_eq!(end.iter().collect::<Vec<_>>(), vec![MACHINES as Bit]);
+    }
+
+    #[test]
+    fn watch_on_another_thread() {
+        // Outputs its input plus one
+        let mut comp = Computer::without_io(vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0]);
+        comp.add_watch(Watch {
+            addrs: 9..=9,
+            access: Access::Write,
+            value: None,
+        });
+        let (mut cin, _) = async_pair(&[41]);
+        let (mut outs, mut cout) = async_pair(&[]);
+
+        // Nothing in the future ties it to this thread
+        let (comp, stops) = thread::spawn(move || {
+            let mut stops = vec![];
+            loop {
+                match block_on(comp.run_async(&mut cin, &mut cout)).unwrap() {
+                    RunState::Watch(hit) => stops.push(hit.ins),
+                    _ => break (comp, stops),
+                }
+            }
+        })
+        .join()
+        .unwrap();
+
+        // Both the input and the add write to the watched address
+        assert_eq!(stops, vec![0, 2]);
+        assert_eq!(comp.steps(), 4);
+        assert_eq!(block_on(outs.next()), Some(42));
+    }
+}
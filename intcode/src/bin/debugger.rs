@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+use std::io::{stdin, stdout};
+use std::process::exit;
+
+use intcode::computer::{Computer, Limits};
+use intcode::debugger::{Command, Debugger, HELP};
+use intcode::input::{Input, Interactive};
+use intcode::output::PrintOutput;
+use intcode::Bit;
+
+/// Well past anything the puzzles touch, but a stray poke or write can't eat all the memory
+const MAX_ADDR: usize = (1 << 24) - 1;
+
+fn usage() -> ! {
+    eprintln!("Usage: debugger <program> [--input 1,2,3]");
+    eprintln!("Without --input every Input instruction prompts for a value");
+    exit(1)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| usage());
+
+    let script = match (args.next().as_deref(), args.next()) {
+        (None, _) => None,
+        (Some("--input"), Some(ins)) => Some(
+            ins.split(',')
+                .map(|i| i.trim().parse::<Bit>())
+                .collect::<Result<VecDeque<_>, _>>()
+                .unwrap_or_else(|_| usage()),
+        ),
+        _ => usage(),
+    };
+
+    let mem = Computer::get_bits(&path)
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1)
+        })
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| usage());
+
+    let mut interactive = Interactive;
+    let mut scripted;
+    let cin: &mut dyn Input = match script {
+        Some(s) => {
+            scripted = s;
+            &mut scripted
+        }
+        None => &mut interactive,
+    };
+    let mut cout = PrintOutput;
+
+    let mut comp = Computer::new(mem, cin, &mut cout);
+    comp.set_limits(Limits {
+        max_addr: Some(MAX_ADDR),
+        ..Limits::default()
+    });
+
+    let mut dbg = Debugger::new(comp);
+    let mut out = stdout();
+
+    println!("{}\n", HELP);
+    dbg.exec(Command::Ins, &mut out).unwrap();
+
+    // Interactive input reads stdin too, so it can't stay locked between commands
+    dbg.repl(|buf| stdin().read_line(buf), &mut out).unwrap();
+}
@@ -0,0 +1,37 @@
+use std::process::exit;
+
+use intcode::computer::Computer;
+use intcode::disasm::listing;
+use intcode::flow::graph;
+
+fn usage() -> ! {
+    eprintln!("Usage: disasm <program> [--dot]");
+    eprintln!("With --dot a Graphviz control flow graph is printed instead of a listing");
+    exit(1)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| usage());
+    let dot = match args.next().as_deref() {
+        None => false,
+        Some("--dot") => true,
+        _ => usage(),
+    };
+
+    let mems = Computer::get_bits(&path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        exit(1)
+    });
+
+    for (i, mem) in mems.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        if dot {
+            print!("{}", graph(mem).to_dot());
+        } else {
+            print!("{}", listing(mem));
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::process::exit;
+
+use intcode::fuzz::{backends, fuzz, Gen};
+
+fn usage() -> ! {
+    eprintln!("Usage: fuzz [--seed <n>] [--runs <n>]");
+    eprintln!("Runs random programs on every backend and prints the smallest one they disagree on");
+    exit(1)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+
+    let mut seed = 0;
+    let mut runs = 10_000;
+    while let Some(flag) = args.next() {
+        let val = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--seed" => seed = val.parse().unwrap_or_else(|_| usage()),
+            "--runs" => runs = val.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+
+    match fuzz(&Gen::default(), seed, runs, &backends()) {
+        None => println!("All the backends agreed on {} programs", runs),
+        Some(m) => {
+            print!("{}", m);
+            exit(1)
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufWriter;
+use std::process::exit;
+
+use intcode::computer::Computer;
+use intcode::output::PrintOutput;
+use intcode::profile::Profile;
+use intcode::Bit;
+
+/// How many of the busiest addresses get listed
+const TOP: usize = 20;
+
+fn usage() -> ! {
+    eprintln!("Usage: profile <program> [--input 1,2,3] [--json <file>]");
+    eprintln!("Runs the program then prints where it spent its time");
+    exit(1)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| usage());
+
+    let mut cin = VecDeque::new();
+    let mut json = None;
+    while let Some(flag) = args.next() {
+        let val = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--input" => {
+                cin = val
+                    .split(',')
+                    .map(|i| i.trim().parse::<Bit>())
+                    .collect::<Result<_, _>>()
+                    .unwrap_or_else(|_| usage())
+            }
+            "--json" => json = Some(val),
+            _ => usage(),
+        }
+    }
+
+    let mem = Computer::get_bits(&path)
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1)
+        })
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| usage());
+
+    let mut cout = PrintOutput;
+    let mut prof = Profile::new();
+
+    let mut comp = Computer::new(mem, &mut cin, &mut cout);
+    comp.set_tracer(Box::new(&mut prof));
+    if let Err(e) = comp.run() {
+        eprintln!("Error at {}: {}", comp.idx(), e);
+    }
+    drop(comp);
+
+    print!("\n{}", prof.report(TOP));
+
+    if let Some(json) = json {
+        let mut w = BufWriter::new(File::create(&json).unwrap_or_else(|e| {
+            eprintln!("Couldn't create {}: {}", json, e);
+            exit(1)
+        }));
+        prof.write_json(&mut w).unwrap();
+    }
+}
@@ -0,0 +1,68 @@
+use std::net::TcpListener;
+use std::process::exit;
+use std::thread;
+
+use intcode::computer::Computer;
+use intcode::tcp;
+
+fn usage() -> ! {
+    eprintln!("Usage: serve <program> [--port <n>]");
+    eprintln!("Runs a fresh copy of the program for every connection, one value per line each way");
+    exit(1)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| usage());
+
+    let mut port = 7019;
+    while let Some(flag) = args.next() {
+        let val = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--port" => port = val.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+
+    let mem = Computer::get_bits(&path)
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1)
+        })
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| usage());
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|e| {
+        eprintln!("Couldn't listen on port {}: {}", port, e);
+        exit(1)
+    });
+    eprintln!("Serving {} on {}", path, listener.local_addr().unwrap());
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Couldn't accept a connection: {}", e);
+                continue;
+            }
+        };
+        let peer = stream
+            .peer_addr()
+            .map_or_else(|_| "?".to_owned(), |a| a.to_string());
+        let mem = mem.clone();
+
+        thread::spawn(move || {
+            let (mut cin, mut cout) = match tcp::split(stream) {
+                Ok(io) => io,
+                Err(e) => return eprintln!("{}: {}", peer, e),
+            };
+
+            let mut comp = Computer::new(mem, &mut cin, &mut cout);
+            match comp.run() {
+                Ok(steps) => eprintln!("{}: halted after {} steps", peer, steps),
+                Err(e) => eprintln!("{}: error at {}: {}", peer, comp.idx(), e),
+            }
+        });
+    }
+}
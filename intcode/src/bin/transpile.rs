@@ -0,0 +1,29 @@
+use std::process::exit;
+
+use intcode::computer::Computer;
+use intcode::transpile::transpile;
+
+fn main() {
+    let path = match std::env::args().nth(1) {
+        Some(p) => p,
+        None => {
+            eprintln!("Usage: transpile <program>");
+            eprintln!("Prints the first program in the file as a Rust module");
+            exit(1)
+        }
+    };
+
+    let mem = Computer::get_bits(&path)
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1)
+        })
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| {
+            eprintln!("{} has no programs in it", path);
+            exit(1)
+        });
+
+    print!("{}", transpile(&mem));
+}
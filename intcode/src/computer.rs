@@ -1,23 +1,297 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
 
+#[cfg(feature = "async")]
+use futures::future::poll_fn;
+
+#[cfg(feature = "async")]
+use crate::async_io::{AsyncInput, AsyncOutput};
 use crate::error::CompError::*;
 use crate::error::{self, Result};
+use crate::ext::{Ext, Handler, Param, SendHandler, UserOp};
 use crate::input::Input;
+use crate::iter::Outputs;
+use crate::memory::Memory;
 use crate::output::Output;
-use crate::{bit_from_bool, Bit};
+use crate::snapshot::Snapshot;
+use crate::trace::{Event, Tracer};
+use crate::watch::{Access, Watch, WatchHit};
+use crate::word::Word;
+use crate::Bit;
+
+pub struct Computer<'a, 'b, W: Word = Bit, M: Memory<W> = Vec<W>, K: Kind<W> = Local> {
+    pub mem: M,
+    idx: usize,
+    rel: W,
+    pending: VecDeque<W>,
+    input: Io<'a, K::Input<'a>>,
+    output: Io<'b, K::Output<'b>>,
+    tracer: Option<Box<K::Tracer<'a>>>,
+    event: Option<Event<W>>,
+    steps: usize,
+    limits: Limits,
+    arith: Arith,
+    watches: Vec<Watch<W>>,
+    watch_hit: Option<WatchHit<W>>,
+    /// Where the instruction being run starts
+    start: usize,
+    journal: VecDeque<Undo<W>>,
+    undo_depth: usize,
+    undo: Option<Undo<W>>,
+    /// Decoded instructions by address, when caching is on
+    cache: Option<DecodeCache>,
+    /// Extra instructions by opcode
+    ops: HashMap<u8, UserOp<'a, W, K>>,
+    trap: Option<Box<K::Handler<'a>>>,
+}
+
+/// A computer that owns its IO and only takes `Send` tracers and handlers, so it can be moved to
+/// another thread; see [`Computer::owned`]
+pub type OwnedComputer<'io, W = Bit, M = Vec<W>> = Computer<'io, 'io, W, M, Sendable>;
+
+/// What the IO, tracer and handlers a computer holds have to be
+pub trait Kind<W: Word> {
+    type Input<'x>: Input<W> + ?Sized;
+    type Output<'x>: Output<W> + ?Sized;
+    type Tracer<'x>: Tracer<W> + ?Sized;
+    type Handler<'x>: FnMut(&mut Ext<'_, W>) -> Result<(), W> + ?Sized;
+
+    /// The IO as handed to an [`Ext`]
+    fn io<'s>(
+        input: &'s mut Self::Input<'_>,
+        output: &'s mut Self::Output<'_>,
+    ) -> (&'s mut dyn Input<W>, &'s mut dyn Output<W>);
+}
+
+/// Anything goes, which is what lets a computer borrow `Rc`s and the like
+pub enum Local {}
+
+/// Everything has to be `Send`, for an [`OwnedComputer`]
+pub enum Sendable {}
+
+impl<W: Word> Kind<W> for Local {
+    type Input<'x> = dyn Input<W> + 'x;
+    type Output<'x> = dyn Output<W> + 'x;
+    type Tracer<'x> = dyn Tracer<W> + 'x;
+    type Handler<'x> = Handler<'x, W>;
+
+    fn io<'s>(
+        input: &'s mut Self::Input<'_>,
+        output: &'s mut Self::Output<'_>,
+    ) -> (&'s mut dyn Input<W>, &'s mut dyn Output<W>) {
+        (input, output)
+    }
+}
+
+impl<W: Word> Kind<W> for Sendable {
+    type Input<'x> = dyn Input<W> + Send + 'x;
+    type Output<'x> = dyn Output<W> + Send + 'x;
+    type Tracer<'x> = dyn Tracer<W> + Send + 'x;
+    type Handler<'x> = SendHandler<'x, W>;
+
+    fn io<'s>(
+        input: &'s mut Self::Input<'_>,
+        output: &'s mut Self::Output<'_>,
+    ) -> (&'s mut dyn Input<W>, &'s mut dyn Output<W>) {
+        (input, output)
+    }
+}
+
+/// The computer's IO, either borrowed from whoever built it or its own
+enum Io<'a, T: ?Sized> {
+    Borrowed(&'a mut T),
+    Owned(Box<T>),
+}
+
+impl<T: ?Sized> Deref for Io<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Io::Borrowed(t) => t,
+            Io::Owned(t) => t,
+        }
+    }
+}
+
+impl<T: ?Sized> DerefMut for Io<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self {
+            Io::Borrowed(t) => t,
+            Io::Owned(t) => t,
+        }
+    }
+}
+
+/// Stands in for the IO of a computer that's only ever driven with [`Computer::resume`], which
+/// never touches it
+struct NoIo;
 
-pub struct Computer<'a, 'b> {
-    pub mem: Vec<Bit>,
+impl<W: Word> Input<W> for NoIo {
+    fn get_in(&mut self) -> Result<W, W> {
+        Err(InputErrStr("This computer has no input of its own"))
+    }
+}
+
+impl<W: Word> Output<W> for NoIo {
+    fn put_out(&mut self, _n: W) -> Result<(), W> {
+        Err(OutputErrStr("This computer has no output of its own"))
+    }
+}
+
+/// Everything needed to put the computer back the way it was before an instruction ran
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+struct Undo<W> {
     idx: usize,
-    rel: Bit,
-    input: &'a mut dyn Input,
-    output: &'b mut dyn Output,
+    rel: W,
+    /// The address written and the value it held before
+    write: Option<(usize, W)>,
+    /// The value taken off the queue of [fed](Computer::feed) values
+    fed: Option<W>,
+    /// Made by [`Computer::poke`] rather than an instruction, so undoing it isn't a step back
+    poke: bool,
+}
+
+/// What `Add`, `Multiply` and `AdjustRel` do when the result doesn't fit in the word.  Either
+/// way the behavior is the same in debug and release builds.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Arith {
+    /// Fail with [`ArithOverflow`](crate::error::CompError::ArithOverflow)
+    #[default]
+    Checked,
+    /// Two's complement wrap around
+    Wrapping,
+}
+
+impl Arith {
+    fn apply<W>(
+        self,
+        a: &W,
+        b: &W,
+        checked: fn(&W, &W) -> Option<W>,
+        wrapping: fn(&W, &W) -> W,
+    ) -> Option<W> {
+        match self {
+            Arith::Checked => checked(a, b),
+            Arith::Wrapping => Some(wrapping(a, b)),
+        }
+    }
+}
+
+/// How often (in steps) the deadline is checked, so we aren't reading the clock constantly
+const DEADLINE_CHECK: usize = 1_024;
+
+/// Decoded instructions by address, for [`Computer::set_cache`].
+///
+/// Clones are cheap and share the decoded instructions until one of the computers using it
+/// modifies its own code, so one cache can be handed to every computer running a program.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Hash)]
+pub struct DecodeCache(Arc<Vec<Option<Instruction>>>);
+
+impl DecodeCache {
+    /// Decodes all the code [`reachable`](crate::disasm::reachable) finds up front.  Leaving the
+    /// data out means writing to it doesn't force a copy of the cache.
+    pub fn new(mem: &[Bit]) -> Self {
+        let mut cache = vec![None; mem.len().min(CACHE_LIMIT)];
+        for (addr, (ins, _)) in crate::disasm::reachable(mem) {
+            if let Some(c) = cache.get_mut(addr) {
+                *c = Some(ins);
+            }
+        }
+        DecodeCache(Arc::new(cache))
+    }
+}
+
+/// Code past this address is never cached, so a jump into far away memory doesn't allocate a huge
+/// cache
+const CACHE_LIMIT: usize = 1 << 20;
+
+/// Guards against runaway programs; anything left as `None` is unlimited
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct Limits {
+    /// The most instructions the computer will ever run
+    pub max_steps: Option<usize>,
+    /// The highest address a write may go to; it bounds where the program writes rather than how
+    /// much memory is in use, which for sparse memory can be far less
+    pub max_addr: Option<usize>,
+    pub deadline: Option<Instant>,
+}
+
+/// Why [`Computer::resume`] handed control back to the caller
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum RunState<W = Bit> {
+    Halted,
+    /// The computer is sitting on an `Input` instruction; `feed` it a value and resume
+    NeedsInput,
+    Output(W),
+    /// The instruction that just ran tripped a watchpoint
+    Watch(WatchHit<W>),
 }
 
 impl Computer<'_, '_> {
     pub fn get_bits<P: AsRef<Path>>(p: P) -> Result<Vec<Vec<Bit>>> {
+        Computer::get_words(p)
+    }
+
+    pub fn new<'pi, 'po>(
+        mem: Vec<Bit>,
+        input: &'pi mut dyn Input,
+        output: &'po mut dyn Output,
+    ) -> Computer<'pi, 'po> {
+        Computer::with_memory(mem, input, output)
+    }
+
+    /// Takes its IO along with it, so with `'static` IO it can be kept in a struct or moved to
+    /// another thread like any other value
+    pub fn owned<'io>(
+        mem: Vec<Bit>,
+        input: impl Input + Send + 'io,
+        output: impl Output + Send + 'io,
+    ) -> OwnedComputer<'io> {
+        Computer::with_memory_owned(mem, input, output)
+    }
+
+    /// Runs lazily, handing out each output as it's made, so there's no IO to set up
+    pub fn outputs<I: IntoIterator<Item = Bit>>(mem: Vec<Bit>, input: I) -> Outputs<I::IntoIter> {
+        Outputs::new(mem, input.into_iter())
+    }
+
+    /// Builds a computer that picks up exactly where the snapshot was taken, using new IO
+    pub fn restore<'pi, 'po>(
+        snap: Snapshot,
+        input: &'pi mut dyn Input,
+        output: &'po mut dyn Output,
+    ) -> Computer<'pi, 'po> {
+        let mut comp = Computer::new(snap.mem, input, output);
+        comp.idx = snap.idx;
+        comp.rel = snap.rel;
+        comp.pending = snap.pending.into();
+        comp
+    }
+}
+
+impl<K: Kind<Bit>> Computer<'_, '_, Bit, Vec<Bit>, K> {
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            mem: self.mem.clone(),
+            idx: self.idx,
+            rel: self.rel,
+            pending: self.pending.iter().copied().collect(),
+        }
+    }
+}
+
+impl<W: Word> Computer<'_, '_, W> {
+    /// Reads every line of the csv file as its own program; use `Computer::<i32>::get_words` and
+    /// friends to load something other than a [`Bit`]
+    pub fn get_words<P: AsRef<Path>>(p: P) -> Result<Vec<Vec<W>>, W> {
         let path = &(*p.as_ref()).to_path_buf();
 
         let rdr = csv::ReaderBuilder::new()
@@ -33,7 +307,7 @@ impl Computer<'_, '_> {
             let mut mem = Vec::with_capacity(rec.len());
             for r in rec.iter() {
                 mem.push(
-                    r.parse::<Bit>()
+                    r.parse::<W>()
                         .map_err(|_| InvalidBitStr(r.to_owned(), path.clone()))?,
                 );
             }
@@ -43,41 +317,547 @@ impl Computer<'_, '_> {
 
         Ok(mems)
     }
+}
 
-    pub fn new<'pi, 'po>(
-        mem: Vec<Bit>,
-        input: &'pi mut dyn Input,
-        output: &'po mut dyn Output,
-    ) -> Computer<'pi, 'po> {
+impl<'a, W: Word, M: Memory<W>> Computer<'a, '_, W, M> {
+    pub fn with_memory<'pi, 'po>(
+        mem: M,
+        input: &'pi mut dyn Input<W>,
+        output: &'po mut dyn Output<W>,
+    ) -> Computer<'pi, 'po, W, M> {
+        Computer::build(mem, Io::Borrowed(input), Io::Borrowed(output))
+    }
+
+    pub fn with_memory_owned<'io>(
+        mem: M,
+        input: impl Input<W> + Send + 'io,
+        output: impl Output<W> + Send + 'io,
+    ) -> OwnedComputer<'io, W, M> {
+        let input: Box<dyn Input<W> + Send + 'io> = Box::new(input);
+        let output: Box<dyn Output<W> + Send + 'io> = Box::new(output);
+        Computer::build(mem, Io::Owned(input), Io::Owned(output))
+    }
+
+    /// For a computer that's only ever [resumed](Computer::resume) or
+    /// run async (with the `async` feature); running it any other way fails at the first IO
+    pub fn without_io(mem: M) -> OwnedComputer<'static, W, M>
+    where
+        W: 'static,
+    {
+        Computer::with_memory_owned(mem, NoIo, NoIo)
+    }
+
+    /// Teaches the computer an extra instruction.  The params say how many words follow the
+    /// opcode and which of them is written, the modes come from the instruction word like
+    /// always.
+    ///
+    /// # Panics
+    ///
+    /// If `opcode` is built in or not two digits, there are more than three params or a
+    /// [`Param::Out`] isn't the last one.
+    pub fn add_op<F>(&mut self, opcode: u8, params: &[Param], handler: F)
+    where
+        F: FnMut(&mut Ext<'_, W>) -> Result<(), W> + 'a,
+    {
+        self.insert_op(opcode, params, Box::new(handler));
+    }
+
+    /// Runs `handler` instead of failing when an instruction isn't a known one
+    pub fn set_trap<F>(&mut self, handler: F)
+    where
+        F: FnMut(&mut Ext<'_, W>) -> Result<(), W> + 'a,
+    {
+        self.trap = Some(Box::new(handler));
+    }
+
+    /// Calls the tracer after every instruction that runs from now on
+    pub fn set_tracer(&mut self, tracer: Box<dyn Tracer<W> + 'a>) {
+        self.tracer = Some(tracer);
+    }
+}
+
+/// The same as for any other computer, but everything has to be `Send`
+impl<'a, W: Word, M: Memory<W>> OwnedComputer<'a, W, M> {
+    /// See [`Computer::add_op`]
+    pub fn add_op<F>(&mut self, opcode: u8, params: &[Param], handler: F)
+    where
+        F: FnMut(&mut Ext<'_, W>) -> Result<(), W> + Send + 'a,
+    {
+        self.insert_op(opcode, params, Box::new(handler));
+    }
+
+    /// See [`Computer::set_trap`]
+    pub fn set_trap<F>(&mut self, handler: F)
+    where
+        F: FnMut(&mut Ext<'_, W>) -> Result<(), W> + Send + 'a,
+    {
+        self.trap = Some(Box::new(handler));
+    }
+
+    /// See [`Computer::set_tracer`]
+    pub fn set_tracer(&mut self, tracer: Box<dyn Tracer<W> + Send + 'a>) {
+        self.tracer = Some(tracer);
+    }
+}
+
+impl<'a, W: Word, M: Memory<W>, K: Kind<W>> Computer<'a, '_, W, M, K> {
+    fn build<'pi, 'po>(
+        mem: M,
+        input: Io<'pi, K::Input<'pi>>,
+        output: Io<'po, K::Output<'po>>,
+    ) -> Computer<'pi, 'po, W, M, K> {
         Computer {
             mem,
             idx: 0,
-            rel: 0,
+            rel: W::from(0),
+            pending: VecDeque::new(),
             input,
             output,
+            tracer: None,
+            event: None,
+            steps: 0,
+            limits: Limits::default(),
+            arith: Arith::default(),
+            watches: vec![],
+            watch_hit: None,
+            start: 0,
+            journal: VecDeque::new(),
+            undo_depth: 0,
+            undo: None,
+            cache: None,
+            ops: HashMap::new(),
+            trap: None,
         }
     }
 
-    pub fn step(&mut self) -> Result<bool> {
+    /// The word at `addr`, or `None` if it's past the end of memory
+    fn word(&self, addr: usize) -> Option<W> {
+        if addr < self.mem.len() {
+            Some(self.mem.read(addr))
+        } else {
+            None
+        }
+    }
+
+    fn check_limits(&self) -> Result<(), W> {
+        let idx = self.idx;
+
+        if let Some(max) = self.limits.max_steps {
+            if self.steps >= max {
+                return Err(StepLimit(idx, max));
+            }
+        }
+
+        if let Some(deadline) = self.limits.deadline {
+            if self.steps % DEADLINE_CHECK == 0 && Instant::now() >= deadline {
+                return Err(Timeout(idx));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn exec(&mut self, yielding: bool) -> Result<Option<RunState<W>>, W> {
+        self.check_limits()?;
+
+        let ins = match self.fetch() {
+            Err(InvalidInstruction(_)) if self.trap.is_some() => return self.run_trap(),
+            ins => ins?,
+        };
         let idx = self.idx;
-        let ins = Instruction::try_from(
-            self.mem
-                .get(idx)
-                .copied()
-                .ok_or_else(|| InvalidIndex(idx))?,
-        )?;
         self.idx += 1;
+        self.start = idx;
+        self.watch_hit = None;
+
+        if self.tracer.is_some() {
+            self.event = Some(Event {
+                addr: idx,
+                cmd: ins.cmd,
+                modes: ins.modes_as()?,
+                args: Vec::with_capacity(3),
+                reads: vec![],
+                write: None,
+                rel: self.rel.clone(),
+            });
+        }
+
+        if self.undo_depth > 0 {
+            self.undo = Some(Undo {
+                idx,
+                rel: self.rel.clone(),
+                write: None,
+                fed: None,
+                poke: false,
+            });
+        }
+
+        let state = ins.step(self, idx, yielding);
+        let event = self.event.take();
+        let undo = self.undo.take();
+        let mut state = state?;
+
+        if state.is_none() && yielding {
+            state = self.watch_hit.clone().map(RunState::Watch);
+        }
+
+        // An input we yielded on hasn't actually run yet
+        if state != Some(RunState::NeedsInput) {
+            self.steps += 1;
+
+            if let Some(undo) = undo {
+                self.journal(undo);
+            }
+
+            if let (Some(ev), Some(tracer)) = (event, self.tracer.as_mut()) {
+                tracer.trace(&ev)?;
+            }
+        }
+
+        Ok(state)
+    }
+
+    /// Hands the unknown instruction at `idx` to the trap, which runs as a step of its own
+    fn run_trap(&mut self) -> Result<Option<RunState<W>>, W> {
+        let at = self.idx;
+        let (input, output) = K::io(&mut self.input, &mut self.output);
+        let mut ext = Ext {
+            at,
+            op: self.mem.read(at),
+            args: vec![],
+            rel: self.rel.clone(),
+            mem: &self.mem,
+            input,
+            output,
+            out: None,
+            jump: None,
+        };
+        if let Some(trap) = self.trap.as_mut() {
+            trap(&mut ext)?;
+        }
+        let jump = ext.jump;
+
+        self.watch_hit = None;
+        self.steps += 1;
+        if self.undo_depth > 0 {
+            self.journal(Undo {
+                idx: at,
+                rel: self.rel.clone(),
+                write: None,
+                fed: None,
+                poke: false,
+            });
+        }
+
+        self.idx = jump.unwrap_or(at + 1);
+        Ok(None)
+    }
+
+    fn insert_op(&mut self, opcode: u8, params: &[Param], handler: Box<K::Handler<'a>>) {
+        assert!(
+            opcode < 100 && Instruction::decode(&W::from(i32::from(opcode))).is_err(),
+            "{} can't be used as an opcode",
+            opcode
+        );
+        assert!(params.len() <= 3, "Instructions take at most three params");
+        assert!(
+            !params.iter().rev().skip(1).any(|p| *p == Param::Out),
+            "Only the last param can be written"
+        );
+
+        self.ops.insert(
+            opcode,
+            UserOp {
+                params: params.to_vec(),
+                handler,
+            },
+        );
+    }
+
+    pub fn remove_op(&mut self, opcode: u8) -> bool {
+        // The cache may still have it decoded
+        self.flush_cache();
+        self.ops.remove(&opcode).is_some()
+    }
+
+    pub fn clear_trap(&mut self) {
+        self.trap = None;
+    }
+
+    pub fn clear_tracer(&mut self) -> Option<Box<K::Tracer<'a>>> {
+        self.tracer.take()
+    }
+
+    /// Watches memory from now on; see [`Computer::watch_hit`]
+    pub fn add_watch(&mut self, watch: Watch<W>) {
+        self.watches.push(watch);
+    }
+
+    pub fn remove_watch(&mut self, i: usize) -> Option<Watch<W>> {
+        if i < self.watches.len() {
+            Some(self.watches.remove(i))
+        } else {
+            None
+        }
+    }
+
+    pub fn watches(&self) -> &[Watch<W>] {
+        &self.watches
+    }
+
+    /// The first watchpoint the last instruction tripped, if any.  [`Computer::resume`] also
+    /// stops with a [`RunState::Watch`] when this happens, `step` and `run` carry on regardless.
+    pub fn watch_hit(&self) -> Option<&WatchHit<W>> {
+        self.watch_hit.as_ref()
+    }
+
+    fn check_watches(&mut self, addr: usize, access: Access, val: &W, cmd: Cmd) {
+        if self.watch_hit.is_none() && self.watches.iter().any(|w| w.matches(addr, access, val)) {
+            self.watch_hit = Some(WatchHit {
+                ins: self.start,
+                cmd,
+                addr,
+                access,
+                value: val.clone(),
+            });
+        }
+    }
+
+    /// Remembers the last `depth` instructions so they can be undone with
+    /// [`Computer::step_back`]; 0 turns the journal off.
+    ///
+    /// Only the computer itself is rewound: outputs already sent stay sent, inputs read from the
+    /// `Input` are gone for good (values [fed](Computer::feed) to it are put back), and memory
+    /// that grew to fit a write doesn't shrink back.
+    pub fn set_undo(&mut self, depth: usize) {
+        self.undo_depth = depth;
+        while self.journal.len() > depth {
+            self.journal.pop_front();
+        }
+    }
+
+    fn journal(&mut self, undo: Undo<W>) {
+        if self.journal.len() == self.undo_depth {
+            self.journal.pop_front();
+        }
+        self.journal.push_back(undo);
+    }
+
+    /// Writes to memory from outside the program, the way a debugger does.  The decode cache is
+    /// kept up to date and, with undo on, the next [`Computer::step_back`] takes the write back
+    /// without undoing an instruction.
+    pub fn poke(&mut self, addr: usize, val: W) -> Result<(), W> {
+        if let Some(max) = self.limits.max_addr {
+            if addr > max {
+                return Err(MemLimit(self.idx, addr, max));
+            }
+        }
+
+        let old = self.mem.read(addr);
+        self.mem.write(addr, val)?;
+        self.invalidate(addr);
+        if self.undo_depth > 0 {
+            self.journal(Undo {
+                idx: self.idx,
+                rel: self.rel.clone(),
+                write: Some((addr, old)),
+                fed: None,
+                poke: true,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Undoes the last instruction that ran (or poke), returning false if there's nothing left to
+    /// undo
+    pub fn step_back(&mut self) -> bool {
+        let undo = match self.journal.pop_back() {
+            Some(u) => u,
+            None => return false,
+        };
+
+        self.idx = undo.idx;
+        self.rel = undo.rel;
+        if let Some((addr, old)) = undo.write {
+            // It was written once already, so it fits
+            let _ = self.mem.write(addr, old);
+            self.invalidate(addr);
+        }
+        if let Some(v) = undo.fed {
+            self.pending.push_front(v);
+        }
+        if !undo.poke {
+            self.steps -= 1;
+        }
+        self.watch_hit = None;
+
+        true
+    }
+
+    /// Steps back until [`Computer::steps`] is `step`.  If the journal doesn't go back that far
+    /// nothing is undone and false is returned.
+    pub fn rewind_to(&mut self, step: usize) -> bool {
+        // Pokes share the journal, so only the instructions in it count
+        let reach = self.journal.iter().filter(|u| !u.poke).count();
+        if step > self.steps || self.steps - step > reach {
+            return false;
+        }
+
+        while self.steps > step {
+            self.step_back();
+        }
+        true
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    pub fn set_arith(&mut self, arith: Arith) {
+        self.arith = arith;
+    }
+
+    /// How many instructions have run over the computer's whole life
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    pub fn rel(&self) -> W {
+        self.rel.clone()
+    }
+
+    /// Decodes the instruction `idx` currently points at without running it
+    pub fn current(&self) -> Result<Instruction, W> {
+        let idx = self.idx;
+        let w = self.word(idx).ok_or_else(|| InvalidIndex(idx))?;
+
+        Instruction::decode(&w).or_else(|e| {
+            let raw = w.to_usize().and_then(|n| u16::try_from(n).ok());
+            let op = raw.map(|r| (r % 100) as u8);
+            match (raw, op.and_then(|op| self.ops.get(&op).map(|u| (op, u)))) {
+                (Some(raw), Some((op, user))) => Ok(Instruction::with_modes(
+                    Cmd::User {
+                        op,
+                        arity: user.params.len() as u8,
+                    },
+                    raw,
+                )),
+                _ => Err(e),
+            }
+        })
+    }
+
+    /// Runs instructions out of the cache instead of decoding them every time; `None` goes back
+    /// to decoding.
+    ///
+    /// Writes made by the program itself keep the cache up to date, but after changing `mem`
+    /// directly call [`Computer::flush_cache`] or the old instructions may still be run.
+    pub fn set_cache(&mut self, cache: Option<DecodeCache>) {
+        self.cache = cache;
+    }
+
+    pub fn cache(&self) -> Option<&DecodeCache> {
+        self.cache.as_ref()
+    }
+
+    pub fn flush_cache(&mut self) {
+        if self.cache.is_some() {
+            self.cache = Some(DecodeCache::default());
+        }
+    }
+
+    fn invalidate(&mut self, addr: usize) {
+        if let Some(cache) = self.cache.as_mut() {
+            if let Some(Some(_)) = cache.0.get(addr) {
+                Arc::make_mut(&mut cache.0)[addr] = None;
+            }
+        }
+    }
+
+    fn fetch(&mut self) -> Result<Instruction, W> {
+        let idx = self.idx;
+
+        if let Some(Some(ins)) = self.cache.as_ref().and_then(|c| c.0.get(idx)) {
+            return Ok(*ins);
+        }
+
+        let ins = self.current()?;
+        let len = self.mem.len();
+
+        // Never copy a shared cache just to add to it
+        if let Some(cache) = self.cache.as_mut().and_then(|c| Arc::get_mut(&mut c.0)) {
+            if idx < CACHE_LIMIT {
+                if idx >= cache.len() {
+                    // Size it for the whole program up front rather than growing it a bit at a time
+                    cache.resize(len.clamp(idx + 1, CACHE_LIMIT), None);
+                }
+                cache[idx] = Some(ins);
+            }
+        }
 
-        ins.step(self)
+        Ok(ins)
     }
 
-    pub fn run(&mut self) -> Result<usize> {
+    pub fn step(&mut self) -> Result<bool, W> {
+        Ok(self.exec(false)? == Some(RunState::Halted))
+    }
+
+    pub fn run(&mut self) -> Result<usize, W> {
         let mut steps = 0;
         while !self.step()? {
             steps += 1;
         }
         Ok(steps + 1)
     }
+
+    /// Queues up a value for the next `Input` instruction hit by [`Computer::resume`]
+    pub fn feed(&mut self, b: W) {
+        self.pending.push_back(b);
+    }
+
+    /// Runs until the computer halts, produces an output, or needs an input that hasn't been
+    /// [`fed`](Computer::feed) to it yet.
+    ///
+    /// Unlike [`Computer::run`] this never touches the `Input`/`Output` the computer was built
+    /// with.  When input is needed `idx` is left on the `Input` instruction so resuming after
+    /// feeding a value picks up right where it left off.
+    pub fn resume(&mut self) -> Result<RunState<W>, W> {
+        loop {
+            if let Some(state) = self.exec(true)? {
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Like [`Computer::run`] but waits on its IO instead of blocking the thread, so it can share
+    /// an executor with any number of others.  The `Input`/`Output` it was built with go unused,
+    /// see [`Computer::without_io`].
+    ///
+    /// Stops when the computer halts or trips a watchpoint, returning [`RunState::Halted`] or
+    /// [`RunState::Watch`]; running it again carries on from the watchpoint.  For an
+    /// [`OwnedComputer`] the future is `Send`, so it can go on a thread pool as well as a local
+    /// executor.
+    #[cfg(feature = "async")]
+    pub async fn run_async(
+        &mut self,
+        input: &mut (dyn AsyncInput<W> + Send),
+        output: &mut (dyn AsyncOutput<W> + Send),
+    ) -> Result<RunState<W>, W> {
+        loop {
+            match self.resume()? {
+                RunState::NeedsInput => {
+                    let v = poll_fn(|cx| input.poll_in(cx)).await?;
+                    self.feed(v);
+                }
+                RunState::Output(v) => poll_fn(|cx| output.poll_out(cx, &v)).await?,
+                stop => return Ok(stop),
+            }
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
@@ -99,8 +879,11 @@ impl Display for Mode {
     }
 }
 
+/// The place value and position of each parameter's mode digit
+const MODE_DIGITS: [(u16, u8); 3] = [(100, 1), (1_000, 2), (10_000, 3)];
+
 impl Mode {
-    fn g(b: u16, n: u16, pos: u8) -> Result<Self> {
+    fn g<W>(b: u16, n: u16, pos: u8) -> Result<Self, W> {
         match (b / n) % 10 {
             0 => Ok(Mode::Position),
             1 => Ok(Mode::Immediate),
@@ -109,89 +892,130 @@ impl Mode {
         }
     }
 
+    #[cfg(test)]
     fn m1(b: u16) -> Result<Self> {
         Mode::g(b, 100, 1)
     }
 
+    #[cfg(test)]
     fn m2(b: u16) -> Result<Self> {
         Mode::g(b, 1_000, 2)
     }
 
+    #[cfg(test)]
     fn m3(b: u16) -> Result<Self> {
         Mode::g(b, 10_000, 3)
     }
 
-    fn get_addr(self, addr: usize, comp: &mut Computer) -> Result<Bit> {
-        // For now don't allow getting memory outside of our range
-        Ok(comp.mem.get(addr).copied().unwrap_or(0))
+    fn get_addr<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        addr: usize,
+        comp: &mut Computer<'_, '_, W, M, K>,
+        cmd: Cmd,
+    ) -> Result<W, W> {
+        if let Some(ev) = comp.event.as_mut() {
+            ev.reads.push(addr);
+        }
+
+        let val = comp.mem.read(addr);
+        comp.check_watches(addr, Access::Read, &val, cmd);
+        Ok(val)
     }
 
-    fn get(self, comp: &mut Computer, cmd: Cmd) -> Result<Bit> {
+    fn get<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        comp: &mut Computer<'_, '_, W, M, K>,
+        cmd: Cmd,
+    ) -> Result<W, W> {
         let idx = comp.idx;
         comp.idx += 1;
 
-        let addr = comp.mem.get(idx).copied().unwrap_or(0);
+        let addr = comp.mem.read(idx);
 
-        match self {
+        let val = match self {
             Mode::Immediate => Ok(addr),
 
-            Mode::Position => usize::try_from(addr)
-                .map_err(|_| InvalidAddress(idx, Some(addr), self, cmd))
-                .and_then(|a| self.get_addr(a, comp)),
-
-            Mode::Relative => usize::try_from(comp.rel + addr)
-                .map_err(|_| InvalidAddress(idx, Some(addr), self, cmd))
-                .and_then(|a| self.get_addr(a, comp)),
+            Mode::Position => addr
+                .to_usize()
+                .ok_or_else(|| InvalidAddress(idx, Some(addr), self, cmd))
+                .and_then(|a| self.get_addr(a, comp, cmd)),
+
+            Mode::Relative => comp
+                .rel
+                .checked_add(&addr)
+                .and_then(|a| a.to_usize())
+                .ok_or_else(|| InvalidAddress(idx, Some(addr), self, cmd))
+                .and_then(|a| self.get_addr(a, comp, cmd)),
+        }?;
+
+        if let Some(ev) = comp.event.as_mut() {
+            ev.args.push(val.clone());
         }
+
+        Ok(val)
     }
 
-    fn addr(self, comp: &mut Computer, cmd: Cmd) -> Result<usize> {
+    fn addr<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        comp: &mut Computer<'_, '_, W, M, K>,
+        cmd: Cmd,
+    ) -> Result<usize, W> {
         let idx = comp.idx;
         let addr = self.get(comp, cmd)?;
-        usize::try_from(addr).map_err(|_| InvalidAddress(idx, Some(addr), self, cmd))
+        addr.to_usize()
+            .ok_or_else(|| InvalidAddress(idx, Some(addr), self, cmd))
     }
 
-    fn put(self, comp: &mut Computer, val: Bit, cmd: Cmd) -> Result<()> {
+    fn put<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        comp: &mut Computer<'_, '_, W, M, K>,
+        val: W,
+        cmd: Cmd,
+    ) -> Result<(), W> {
         let idx = comp.idx;
         comp.idx += 1;
 
         let abit = match self {
             Mode::Position => comp
-                .mem
-                .get(idx)
-                .copied()
+                .word(idx)
                 .ok_or_else(|| InvalidAddress(idx, None, self, cmd))?,
 
             Mode::Relative => {
+                let off = comp
+                    .word(idx)
+                    .ok_or_else(|| InvalidAddress(idx, None, self, cmd))?;
                 comp.rel
-                    + comp
-                        .mem
-                        .get(idx)
-                        .copied()
-                        .ok_or_else(|| InvalidAddress(idx, None, self, cmd))?
+                    .checked_add(&off)
+                    .ok_or_else(|| InvalidAddress(idx, Some(off), self, cmd))?
             }
 
             Mode::Immediate => return Err(InvalidOutputMode(idx, cmd)),
         };
 
-        usize::try_from(abit)
-            .map_err(|_| InvalidAddress(idx, Some(abit), self, cmd))
-            .and_then(|a| {
-                if comp.mem.len() < a {
-                    comp.mem.resize(a + 2, 0);
-                }
+        let a = abit
+            .to_usize()
+            .ok_or_else(|| InvalidAddress(idx, Some(abit), self, cmd))?;
 
-                comp.mem
-                    .get_mut(a)
-                    .ok_or_else(|| InvalidAddress(idx, Some(abit), self, cmd))
-                    .map(|o| {
-                        *o = val;
-                    })
-            })
+        if let Some(max) = comp.limits.max_addr {
+            if a > max {
+                return Err(MemLimit(idx, a, max));
+            }
+        }
+
+        if let Some(ev) = comp.event.as_mut() {
+            ev.write = Some((a, val.clone()));
+        }
+
+        comp.check_watches(a, Access::Write, &val, cmd);
+        comp.invalidate(a);
+        if let Some(undo) = comp.undo.as_mut() {
+            undo.write = Some((a, comp.mem.read(a)));
+        }
+        comp.mem.write(a, val)
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
 pub enum Cmd {
     Add,
     Multiply,
@@ -203,6 +1027,11 @@ pub enum Cmd {
     Equals,
     AdjustRel,
     Halt,
+    /// One added with [`Computer::add_op`]
+    User {
+        op: u8,
+        arity: u8,
+    },
 }
 
 impl Display for Cmd {
@@ -220,30 +1049,128 @@ impl Display for Cmd {
             Equals => "Equals",
             AdjustRel => "Adjust Relative Base",
             Halt => "Halt",
+            User { op, .. } => return write!(f, "User {}", op),
         })
     }
 }
 
+impl FromStr for Cmd {
+    type Err = String;
+
+    /// Accepts either the opcode number or the (case insensitive) mnemonic
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        use Cmd::*;
+
+        if let Ok(n) = s.parse::<u16>() {
+            return Instruction::try_from(n as Bit)
+                .map(|i| i.cmd)
+                .map_err(|e| e.to_string());
+        }
+
+        [
+            Add, Multiply, Input, Output, JumpTrue, JumpFalse, LessThan, Equals, AdjustRel, Halt,
+        ]
+        .iter()
+        .copied()
+        .find(|c| c.mnemonic().eq_ignore_ascii_case(s))
+        .ok_or_else(|| format!("Unknown cmd: {}", s))
+    }
+}
+
 impl Cmd {
     #[inline]
     pub fn is_stop(self) -> bool {
         self == Cmd::Halt
     }
+
+    pub fn opcode(self) -> u16 {
+        use Cmd::*;
+
+        match self {
+            Add => 1,
+            Multiply => 2,
+            Input => 3,
+            Output => 4,
+            JumpTrue => 5,
+            JumpFalse => 6,
+            LessThan => 7,
+            Equals => 8,
+            AdjustRel => 9,
+            Halt => 99,
+            User { op, .. } => u16::from(op),
+        }
+    }
+
+    /// User ops are named after their opcode, like `user42`, so no two share a name
+    pub fn mnemonic(self) -> Cow<'static, str> {
+        use Cmd::*;
+
+        Cow::Borrowed(match self {
+            Add => "add",
+            Multiply => "multiply",
+            Input => "input",
+            Output => "output",
+            JumpTrue => "jumptrue",
+            JumpFalse => "jumpfalse",
+            LessThan => "lessthan",
+            Equals => "equals",
+            AdjustRel => "adjustrel",
+            Halt => "halt",
+            User { op, .. } => return Cow::Owned(format!("user{}", op)),
+        })
+    }
+
+    /// How many parameters follow the opcode
+    pub fn arity(self) -> usize {
+        use Cmd::*;
+
+        match self {
+            Add | Multiply | LessThan | Equals => 3,
+            JumpTrue | JumpFalse => 2,
+            Input | Output | AdjustRel => 1,
+            Halt => 0,
+            User { arity, .. } => usize::from(arity),
+        }
+    }
+
+    /// Whether the cmd writes through its nth param, so it can't be in immediate mode. User ops
+    /// can write wherever they like, so they never count.
+    pub fn writes(self, param: usize) -> bool {
+        use Cmd::*;
+
+        match self {
+            Input => param == 0,
+            Add | Multiply | LessThan | Equals => param == 2,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
-struct Instruction {
+pub struct Instruction {
     cmd: Cmd,
     raw: u16,
+    /// Decoded up front for the params the cmd takes; `None` when the digit is invalid
+    modes: [Option<Mode>; 3],
 }
 
 impl TryFrom<Bit> for Instruction {
     type Error = error::CompError;
 
     fn try_from(b: Bit) -> Result<Self> {
+        Instruction::decode(&b)
+    }
+}
+
+impl Instruction {
+    /// Decodes an instruction from any kind of word
+    pub fn decode<W: Word>(w: &W) -> Result<Self, W> {
         use Cmd::*;
 
-        let raw = u16::try_from(b).map_err(|_| InvalidInstruction(b))?;
+        let raw = w
+            .to_usize()
+            .and_then(|n| u16::try_from(n).ok())
+            .ok_or_else(|| InvalidInstruction(w.clone()))?;
 
         let cmd = match raw % 100 {
             1 => Add,
@@ -256,60 +1183,137 @@ impl TryFrom<Bit> for Instruction {
             8 => Equals,
             9 => AdjustRel,
             99 => Halt,
-            n => return Err(InvalidInstruction(n as Bit)),
+            n => return Err(InvalidInstruction(W::from(i32::from(n)))),
         };
 
-        Ok(Instruction { cmd, raw })
+        Ok(Instruction::with_modes(cmd, raw))
     }
-}
 
-impl Instruction {
-    fn m1(self) -> Result<Mode> {
-        Mode::m1(self.raw)
+    fn with_modes(cmd: Cmd, raw: u16) -> Self {
+        let mut modes = [None; 3];
+        for (m, &(n, pos)) in modes.iter_mut().zip(&MODE_DIGITS).take(cmd.arity()) {
+            *m = Mode::g::<Bit>(raw, n, pos).ok();
+        }
+
+        Instruction { cmd, raw, modes }
+    }
+
+    pub fn cmd(self) -> Cmd {
+        self.cmd
+    }
+
+    pub fn raw(self) -> u16 {
+        self.raw
+    }
+
+    /// The mode of every parameter the cmd takes, in order
+    pub fn modes(self) -> Result<Vec<Mode>> {
+        self.modes_as()
+    }
+
+    fn modes_as<W>(self) -> Result<Vec<Mode>, W> {
+        (0..self.cmd.arity()).map(|i| self.mode(i)).collect()
+    }
+
+    fn mode<W>(self, i: usize) -> Result<Mode, W> {
+        match self.modes[i] {
+            Some(m) => Ok(m),
+            None => {
+                let (n, pos) = MODE_DIGITS[i];
+                Mode::g(self.raw, n, pos)
+            }
+        }
+    }
+
+    fn m1<W>(self) -> Result<Mode, W> {
+        self.mode(0)
     }
-    fn m2(self) -> Result<Mode> {
-        Mode::m2(self.raw)
+    fn m2<W>(self) -> Result<Mode, W> {
+        self.mode(1)
     }
-    fn m3(self) -> Result<Mode> {
-        Mode::m3(self.raw)
+    fn m3<W>(self) -> Result<Mode, W> {
+        self.mode(2)
     }
 
-    fn get_m1(self, comp: &mut Computer) -> Result<Bit> {
+    fn get_m1<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        comp: &mut Computer<'_, '_, W, M, K>,
+    ) -> Result<W, W> {
         self.m1()?.get(comp, self.cmd)
     }
-    fn get_m2(self, comp: &mut Computer) -> Result<Bit> {
+    fn get_m2<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        comp: &mut Computer<'_, '_, W, M, K>,
+    ) -> Result<W, W> {
         self.m2()?.get(comp, self.cmd)
     }
-    fn put_m3(self, comp: &mut Computer, v: Bit) -> Result<()> {
+    fn put_m3<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        comp: &mut Computer<'_, '_, W, M, K>,
+        v: W,
+    ) -> Result<(), W> {
         self.m3()?.put(comp, v, self.cmd)
     }
 
-    fn step(self, comp: &mut Computer) -> Result<bool> {
+    fn step<W: Word, M: Memory<W>, K: Kind<W>>(
+        self,
+        comp: &mut Computer<'_, '_, W, M, K>,
+        start: usize,
+        yielding: bool,
+    ) -> Result<Option<RunState<W>>, W> {
         use Cmd::*;
         match self.cmd {
             Add => {
-                let sum = self.get_m1(comp)? + self.get_m2(comp)?;
+                let (a, b) = (self.get_m1(comp)?, self.get_m2(comp)?);
+                let sum = comp
+                    .arith
+                    .apply(&a, &b, W::checked_add, W::wrapping_add)
+                    .ok_or_else(|| ArithOverflow(start, self.cmd, a, b))?;
                 self.put_m3(comp, sum)?;
             }
 
             Multiply => {
-                let prod = self.get_m1(comp)? * self.get_m2(comp)?;
+                let (a, b) = (self.get_m1(comp)?, self.get_m2(comp)?);
+                let prod = comp
+                    .arith
+                    .apply(&a, &b, W::checked_mul, W::wrapping_mul)
+                    .ok_or_else(|| ArithOverflow(start, self.cmd, a, b))?;
                 self.put_m3(comp, prod)?;
             }
 
             Input => {
-                let ival = comp.input.get_in()?;
+                let ival = if yielding {
+                    match comp.pending.pop_front() {
+                        Some(v) => {
+                            if let Some(undo) = comp.undo.as_mut() {
+                                undo.fed = Some(v.clone());
+                            }
+                            v
+                        }
+                        None => {
+                            comp.idx = start;
+                            return Ok(Some(RunState::NeedsInput));
+                        }
+                    }
+                } else {
+                    comp.input.at(comp.steps, start);
+                    comp.input.get_in()?
+                };
                 self.m1()?.put(comp, ival, self.cmd)?;
             }
 
             Output => {
                 let oval = self.get_m1(comp)?;
+                if yielding {
+                    return Ok(Some(RunState::Output(oval)));
+                }
+                comp.output.at(comp.steps, start);
                 comp.output.put_out(oval)?;
             }
 
             JumpTrue => {
                 let tval = self.get_m1(comp)?;
-                if tval != 0 {
+                if !tval.is_zero() {
                     let addr = self.m2()?.addr(comp, self.cmd)?;
                     comp.idx = addr;
                 } else {
@@ -319,7 +1323,7 @@ impl Instruction {
 
             JumpFalse => {
                 let tval = self.get_m1(comp)?;
-                if tval == 0 {
+                if tval.is_zero() {
                     let addr = self.m2()?.addr(comp, self.cmd)?;
                     comp.idx = addr;
                 } else {
@@ -329,22 +1333,67 @@ impl Instruction {
 
             LessThan => {
                 let less = self.get_m1(comp)? < self.get_m2(comp)?;
-                self.put_m3(comp, bit_from_bool(less))?;
+                self.put_m3(comp, W::from(less as i32))?;
             }
 
             Equals => {
                 let less = self.get_m1(comp)? == self.get_m2(comp)?;
-                self.put_m3(comp, bit_from_bool(less))?;
+                self.put_m3(comp, W::from(less as i32))?;
             }
 
             AdjustRel => {
-                comp.rel += self.get_m1(comp)?;
+                let (a, b) = (comp.rel.clone(), self.get_m1(comp)?);
+                comp.rel = comp
+                    .arith
+                    .apply(&a, &b, W::checked_add, W::wrapping_add)
+                    .ok_or_else(|| ArithOverflow(start, self.cmd, a, b))?;
+            }
+
+            Halt => {
+                // Stay on the halt so stepping/resuming a finished computer is a no-op
+                comp.idx = start;
+                return Ok(Some(RunState::Halted));
             }
 
-            Halt => return Ok(true),
+            User { op, arity } => {
+                let unknown = || InvalidInstruction(W::from(i32::from(op)));
+                let writes =
+                    comp.ops.get(&op).ok_or_else(unknown)?.params.last() == Some(&Param::Out);
+                let reads = usize::from(arity) - writes as usize;
+
+                let mut args = Vec::with_capacity(reads);
+                for i in 0..reads {
+                    args.push(self.mode(i)?.get(comp, self.cmd)?);
+                }
+
+                let (input, output) = K::io(&mut comp.input, &mut comp.output);
+                let mut ext = Ext {
+                    at: start,
+                    op: comp.mem.read(start),
+                    args,
+                    rel: comp.rel.clone(),
+                    mem: &comp.mem,
+                    input,
+                    output,
+                    out: None,
+                    jump: None,
+                };
+                (comp.ops.get_mut(&op).ok_or_else(unknown)?.handler)(&mut ext)?;
+                let (out, jump) = (ext.out, ext.jump);
+
+                if writes {
+                    match out {
+                        Some(v) => self.mode(reads)?.put(comp, v, self.cmd)?,
+                        None => comp.idx += 1,
+                    }
+                }
+                if let Some(to) = jump {
+                    comp.idx = to;
+                }
+            }
         };
 
-        Ok(false)
+        Ok(None)
     }
 }
 
@@ -386,6 +1435,21 @@ mod test {
         }
     }
 
+    #[test]
+    fn decode() {
+        let ins = Instruction::try_from(21_107).unwrap();
+        assert_eq!(ins.cmd(), Cmd::LessThan);
+        assert_eq!(
+            ins.modes().unwrap(),
+            vec![Mode::Immediate, Mode::Immediate, Mode::Relative]
+        );
+
+        assert_eq!(Instruction::try_from(99).unwrap().modes().unwrap(), vec![]);
+        assert_eq!("JumpFalse".parse::<Cmd>().unwrap(), Cmd::JumpFalse);
+        assert_eq!("9".parse::<Cmd>().unwrap(), Cmd::AdjustRel);
+        assert!("10".parse::<Cmd>().is_err());
+    }
+
     #[test]
     fn simple() {
         use std::collections::VecDeque;
@@ -433,4 +1497,484 @@ mod test {
 
         test_proc(&[104, 1_125_899_906_842_624, 99], &[1_125_899_906_842_624]);
     }
+
+    #[test]
+    fn resume() {
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+
+        let mut c = Computer::new(vec![3, 9, 4, 9, 3, 9, 4, 9, 99, 0], &mut cin, &mut cout);
+
+        assert_eq!(c.resume().unwrap(), RunState::NeedsInput);
+        assert_eq!(c.resume().unwrap(), RunState::NeedsInput);
+
+        c.feed(7);
+        c.feed(8);
+        assert_eq!(c.resume().unwrap(), RunState::Output(7));
+        assert_eq!(c.resume().unwrap(), RunState::Output(8));
+        assert_eq!(c.resume().unwrap(), RunState::Halted);
+        assert_eq!(c.resume().unwrap(), RunState::Halted);
+    }
+
+    #[test]
+    fn snapshot_fork() {
+        let mem = vec![3, 11, 1002, 11, 2, 11, 4, 11, 1105, 1, 0, 0];
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+
+        let mut c = Computer::new(mem, &mut cin, &mut cout);
+        c.feed(5);
+        assert_eq!(c.resume().unwrap(), RunState::Output(10));
+        assert_eq!(c.resume().unwrap(), RunState::NeedsInput);
+
+        let snap = c.snapshot();
+
+        let mut fin = VecDeque::from(vec![21]);
+        let mut fout = vec![];
+        let mut fork = Computer::restore(snap.clone(), &mut fin, &mut fout);
+        assert!(fork.step().is_ok());
+        assert_eq!(fork.resume().unwrap(), RunState::Output(42));
+
+        c.feed(1);
+        assert_eq!(c.resume().unwrap(), RunState::Output(2));
+        assert_eq!(c.resume().unwrap(), RunState::NeedsInput);
+        assert_eq!(c.snapshot().idx, snap.idx);
+    }
+
+    #[test]
+    fn limits() {
+        use std::time::Duration;
+
+        fn run(mem: &[Bit], limits: Limits) -> (Result<usize>, usize) {
+            let mut cin = VecDeque::new();
+            let mut cout = vec![];
+
+            let mut c = Computer::new(mem.to_vec(), &mut cin, &mut cout);
+            c.set_limits(limits);
+            (c.run(), c.steps())
+        }
+
+        // An endless loop that keeps storing further and further away
+        let mem = [109, 1, 21101, 0, 0, 1000, 1105, 1, 0];
+
+        let limits = Limits {
+            max_steps: Some(10),
+            ..Limits::default()
+        };
+        match run(&mem, limits) {
+            (Err(StepLimit(2, 10)), 10) => (),
+            other => panic!("Expected a step limit, got {:?}", other),
+        }
+
+        let limits = Limits {
+            max_addr: Some(1_002),
+            ..Limits::default()
+        };
+        match run(&mem, limits) {
+            (Err(MemLimit(5, 1_003, 1_002)), _) => (),
+            other => panic!("Expected a memory limit, got {:?}", other),
+        }
+
+        let limits = Limits {
+            deadline: Some(Instant::now() + Duration::from_millis(10)),
+            ..Limits::default()
+        };
+        match run(&[1105, 1, 0], limits) {
+            (Err(Timeout(0)), _) => (),
+            other => panic!("Expected a timeout, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        fn run(mem: Vec<Bit>, arith: Arith) -> Result<Vec<Bit>> {
+            let mut cin = VecDeque::new();
+            let mut cout = vec![];
+
+            let mut c = Computer::new(mem, &mut cin, &mut cout);
+            c.set_arith(arith);
+            c.run()?;
+
+            drop(c);
+            Ok(cout)
+        }
+
+        let big = 1 << 62;
+        let mem = vec![1102, big, 4, 7, 4, 7, 99, 0];
+
+        match run(mem.clone(), Arith::Checked) {
+            Err(ArithOverflow(0, Cmd::Multiply, a, 4)) => assert_eq!(a, big),
+            other => panic!("Expected an overflow, got {:?}", other),
+        }
+
+        assert_eq!(run(mem.clone(), Arith::Wrapping).unwrap(), vec![0]);
+
+        match run(vec![109, Bit::MAX, 109, 1, 99], Arith::Checked) {
+            Err(ArithOverflow(2, Cmd::AdjustRel, Bit::MAX, 1)) => (),
+            other => panic!("Expected an overflow, got {:?}", other),
+        }
+
+        // Plenty of room in a wider word
+        let (mut cin, mut cout) = (VecDeque::new(), vec![]);
+        let wide: Vec<i128> = mem.into_iter().map(i128::from).collect();
+        Computer::with_memory(wide, &mut cin, &mut cout)
+            .run()
+            .unwrap();
+        assert_eq!(cout, vec![i128::from(big) * 4]);
+    }
+
+    #[test]
+    fn paged_memory() {
+        use crate::memory::Paged;
+
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+
+        // Stash a value way off in the distance and read it back
+        let mem: Paged = Paged::from(vec![1101, 20, 22, 1 << 40, 4, 1 << 40, 99]);
+        let mut c = Computer::with_memory(mem, &mut cin, &mut cout);
+        c.run().unwrap();
+
+        assert_eq!(c.mem.pages(), 2);
+        drop(c);
+        assert_eq!(cout, vec![42]);
+    }
+
+    #[test]
+    fn resume_feedback_loop() {
+        let mem = [
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+
+        let mut ins: Vec<VecDeque<Bit>> = (0..5).map(|_| VecDeque::new()).collect();
+        let mut outs: Vec<Vec<Bit>> = (0..5).map(|_| vec![]).collect();
+
+        let mut amps: Vec<Computer> = ins
+            .iter_mut()
+            .zip(outs.iter_mut())
+            .map(|(i, o)| Computer::new(mem.to_vec(), i, o))
+            .collect();
+
+        for (amp, phase) in amps.iter_mut().zip(&[9, 8, 7, 6, 5]) {
+            amp.feed(*phase);
+        }
+
+        let mut signal = 0;
+        'ring: loop {
+            for amp in amps.iter_mut() {
+                amp.feed(signal);
+                match amp.resume().unwrap() {
+                    RunState::Output(o) => signal = o,
+                    RunState::Halted => break 'ring,
+                    RunState::NeedsInput => panic!("Amp starved for input"),
+                    RunState::Watch(_) => panic!("No watchpoints were set"),
+                }
+            }
+        }
+
+        assert_eq!(signal, 139_629_729);
+    }
+
+    #[test]
+    fn watchpoints() {
+        use crate::watch::{Access, Watch, WatchHit};
+
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+        let mut c = Computer::new(
+            vec![1101, 2, 3, 11, 4, 11, 1001, 11, 5, 11, 99, 0],
+            &mut cin,
+            &mut cout,
+        );
+        c.add_watch(Watch {
+            addrs: 11..=11,
+            access: Access::Write,
+            value: Some(10),
+        });
+        c.add_watch(Watch {
+            addrs: 8..=12,
+            access: Access::Read,
+            value: None,
+        });
+
+        // The output wins but the read is still recorded
+        assert_eq!(c.resume().unwrap(), RunState::Output(5));
+        assert_eq!(
+            c.watch_hit().map(|h| (h.ins, h.cmd)),
+            Some((4, Cmd::Output))
+        );
+
+        // The read comes before the write so it's the one reported
+        assert_eq!(
+            c.resume().unwrap(),
+            RunState::Watch(WatchHit {
+                ins: 6,
+                cmd: Cmd::Add,
+                addr: 11,
+                access: Access::Read,
+                value: 5,
+            })
+        );
+
+        // Run the add again with only the write watched
+        c.remove_watch(1);
+        c.idx = 6;
+        c.mem[11] = 5;
+        match c.resume().unwrap() {
+            RunState::Watch(hit) => assert_eq!((hit.access, hit.value), (Access::Write, 10)),
+            other => panic!("Expected a watchpoint, got {:?}", other),
+        }
+        assert_eq!(c.resume().unwrap(), RunState::Halted);
+    }
+
+    #[test]
+    fn undo() {
+        let mem = vec![3, 11, 1002, 11, 2, 11, 4, 11, 1105, 1, 0, 0];
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+
+        let mut c = Computer::new(mem, &mut cin, &mut cout);
+        c.set_undo(100);
+        c.feed(5);
+        let before = c.snapshot();
+
+        assert_eq!(c.resume().unwrap(), RunState::Output(10));
+        assert_eq!(c.steps(), 3);
+
+        // Back to before the multiply
+        assert!(c.step_back());
+        assert!(c.step_back());
+        assert_eq!((c.idx(), c.mem[11]), (2, 5));
+
+        assert!(c.rewind_to(0));
+        assert_eq!(c.snapshot(), before);
+        assert!(!c.step_back());
+
+        // Only the last two instructions are kept
+        assert_eq!(c.resume().unwrap(), RunState::Output(10));
+        c.set_undo(2);
+        assert!(!c.rewind_to(0));
+        assert!(c.rewind_to(1));
+        assert_eq!(c.idx(), 2);
+        assert_eq!(c.resume().unwrap(), RunState::Output(10));
+    }
+
+    #[test]
+    fn rewind_past_poke() {
+        let mem = vec![3, 11, 1002, 11, 2, 11, 4, 11, 1105, 1, 0, 0];
+        let mut cin = VecDeque::from(vec![5]);
+        let mut cout = vec![];
+
+        // The input runs before there's a journal, leaving it with [poke, multiply, output]
+        let mut c = Computer::new(mem, &mut cin, &mut cout);
+        c.step().unwrap();
+        c.set_undo(100);
+        c.poke(10, 7).unwrap();
+        assert_eq!(c.resume().unwrap(), RunState::Output(10));
+        let after = c.snapshot();
+
+        // The input can't be undone so nothing is
+        assert!(!c.rewind_to(0));
+        assert_eq!((c.snapshot(), c.steps()), (after, 3));
+
+        // Stops after the multiply, which came after the poke
+        assert!(c.rewind_to(1));
+        assert_eq!((c.idx(), c.steps(), c.mem[10], c.mem[11]), (2, 1, 7, 5));
+
+        assert!(c.step_back());
+        assert_eq!((c.steps(), c.mem[10]), (1, 0));
+        assert!(!c.step_back());
+    }
+
+    #[test]
+    fn decode_cache() {
+        // Runs an add, then rewrites it into a multiply and runs it again
+        let mem = vec![
+            1101, 3, 3, 21, 4, 21, 1008, 0, 1102, 22, 1005, 22, 20, 1001, 0, 1, 0, 1105, 1, 0, 99,
+            0, 0,
+        ];
+
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+        let mut c = Computer::new(mem.clone(), &mut cin, &mut cout);
+        c.set_cache(Some(DecodeCache::new(&mem)));
+        let shared = c.cache().cloned().unwrap();
+        c.run().unwrap();
+
+        // The computer copied the cache rather than change it under anyone else
+        assert_eq!(shared, DecodeCache::new(&mem));
+
+        // Poking code directly needs a flush
+        c.mem = mem;
+        c.mem[1] = 4;
+        c.idx = 0;
+        c.flush_cache();
+        c.run().unwrap();
+
+        drop(c);
+        assert_eq!(cout, vec![6, 9, 7, 12]);
+    }
+
+    #[test]
+    fn other_words() {
+        let path = format!("{}/../day_09/input", env!("CARGO_MANIFEST_DIR"));
+
+        let mut cin = VecDeque::from(vec![1]);
+        let mut cout = vec![];
+        let mem = Computer::<i128>::get_words(&path).unwrap().remove(0);
+        Computer::with_memory(mem, &mut cin, &mut cout)
+            .run()
+            .unwrap();
+        assert_eq!(cout, vec![2_752_191_671]);
+
+        // Too big for an i32
+        let mem: Vec<i32> = vec![1102, 1 << 30, 4, 7, 4, 7, 99, 0];
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+        let mut c = Computer::with_memory(mem, &mut cin, &mut cout);
+        match c.run() {
+            Err(ArithOverflow(0, Cmd::Multiply, a, 4)) => assert_eq!(a, 1 << 30),
+            other => panic!("Expected an overflow, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_words() {
+        use num_bigint::BigInt;
+
+        let path = format!("{}/../day_09/input", env!("CARGO_MANIFEST_DIR"));
+
+        let mut cin = VecDeque::from(vec![BigInt::from(1)]);
+        let mut bout = vec![];
+        let mem = Computer::<BigInt>::get_words(&path).unwrap().remove(0);
+        Computer::with_memory(mem, &mut cin, &mut bout)
+            .run()
+            .unwrap();
+        assert_eq!(bout, vec![BigInt::from(2_752_191_671i64)]);
+
+        // Too big for an i32, but the bigint doesn't mind
+        let mem: Vec<BigInt> = [1102, 1 << 30, 4, 7, 4, 7, 99, 0]
+            .iter()
+            .map(|&n| BigInt::from(n))
+            .collect();
+        let mut cin = VecDeque::new();
+        let mut bout = vec![];
+        Computer::with_memory(mem, &mut cin, &mut bout)
+            .run()
+            .unwrap();
+        assert_eq!(bout, vec![BigInt::from(1i64 << 32)]);
+    }
+
+    #[test]
+    fn user_ops() {
+        let mem = vec![
+            1142, 10, 3, 20, // [20] = 10 - 3
+            43, 20, // log [20]
+            77, // trap
+            144, 10, // jump to 10
+            99, //
+            4, 20, //
+            99,
+        ];
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+        let mut log = vec![];
+        let mut traps = vec![];
+
+        let mut c = Computer::new(mem.clone(), &mut cin, &mut cout);
+        c.set_undo(10);
+        c.add_op(42, &[Param::In, Param::In, Param::Out], |ext| {
+            ext.out = Some(ext.args[0] - ext.args[1]);
+            Ok(())
+        });
+        c.add_op(43, &[Param::In], |ext| {
+            log.push(ext.args[0]);
+            Ok(())
+        });
+        c.add_op(44, &[Param::In], |ext| {
+            ext.jump = Some(ext.args[0] as usize);
+            Ok(())
+        });
+        c.set_trap(|ext| {
+            traps.push((ext.at, ext.op));
+            Ok(())
+        });
+
+        assert_eq!(c.run().unwrap(), 6);
+        assert_eq!(c.mem[20], 7);
+
+        // The write went through the journal like any other
+        assert!(c.rewind_to(0));
+        assert_eq!(c.mem.len(), 21);
+        assert_eq!(c.mem[20], 0);
+
+        assert!(c.remove_op(44));
+        c.clear_trap();
+        match c.run() {
+            Err(InvalidInstruction(77)) => (),
+            other => panic!("Expected the trap to be gone, got {:?}", other),
+        }
+        drop(c);
+
+        assert_eq!(cout, vec![7]);
+        assert_eq!(log, vec![7, 7]);
+        assert_eq!(traps, vec![(6, 77)]);
+    }
+
+    #[test]
+    fn owned_io() {
+        use crate::input::FnInput;
+        use crate::output::FnOutput;
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::sync::mpsc;
+
+        // Nothing borrowed, so it can live in a struct and be handed off to another thread
+        struct Doubler {
+            comp: OwnedComputer<'static>,
+        }
+
+        // Outputs each input doubled until it's handed a zero
+        let mem = vec![
+            3, 15, 1006, 15, 14, 1002, 15, 2, 15, 4, 15, 1105, 1, 0, 99, 0,
+        ];
+        let (send, recv) = mpsc::channel();
+        let mut next = 3;
+        let mut d = Doubler {
+            comp: Computer::owned(
+                mem,
+                FnInput(move || {
+                    next -= 1;
+                    Ok(next)
+                }),
+                FnOutput(move |v| send.send(v).map_err(|e| OutputErr(Box::new(e)))),
+            ),
+        };
+
+        let steps = std::thread::spawn(move || d.comp.run().unwrap())
+            .join()
+            .unwrap();
+        assert!(steps > 0);
+        assert_eq!(recv.iter().collect::<Vec<_>>(), vec![4, 2]);
+
+        // Borrowed IO and anything set on its computer don't have to be Send
+        let reads = Rc::new(Cell::new(0));
+        let counted = Rc::clone(&reads);
+        let mut cin = FnInput(move || {
+            counted.set(counted.get() + 1);
+            Ok(5)
+        });
+        let mut cout = vec![];
+        let mut comp = Computer::new(vec![3, 0, 4, 0, 99], &mut cin, &mut cout);
+        let traced = Rc::clone(&reads);
+        comp.set_trap(move |_| {
+            traced.set(0);
+            Ok(())
+        });
+        comp.run().unwrap();
+        drop(comp);
+        assert_eq!((reads.get(), cout), (1, vec![5]));
+    }
 }
@@ -0,0 +1,599 @@
+use std::collections::{BTreeSet, HashSet};
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::computer::{Cmd, Computer};
+use crate::memory::Memory;
+use crate::watch::{self, Access};
+use crate::Bit;
+
+pub const HELP: &str = "\
+s, step [n]            run n instructions (default 1)
+bk, back [n]           undo the last n instructions or pokes (default 1)
+rw, rewind <step>      undo instructions until only step have run
+c, continue            run until a breakpoint or halt
+b, break <addr>        break when idx reaches addr
+bo, breakop <cmd>      break before any cmd (mnemonic or opcode)
+d, delete <addr|cmd>   remove a breakpoint
+w, watch <addr[..end]> [r|w|rw] [value]
+                       stop after memory is read or written (default rw), optionally
+                       only when the value matches
+uw, unwatch <n>        remove the nth watchpoint shown by list
+l, list                list breakpoints and watchpoints
+r, regs                print idx and rel
+i, ins                 show the current instruction
+x, dump <addr> [len]   dump len (default 8) memory cells starting at addr
+p, poke <addr> <v>...  write values into memory starting at addr
+h, help                show this help
+q, quit                exit the debugger";
+
+/// How many instructions can be stepped back over
+const UNDO_DEPTH: usize = 100_000;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Command {
+    Step(usize),
+    Back(usize),
+    Rewind(usize),
+    Continue,
+    Break(usize),
+    BreakOp(Cmd),
+    DeleteAddr(usize),
+    DeleteOp(Cmd),
+    Watch(watch::Watch),
+    Unwatch(usize),
+    List,
+    Regs,
+    Ins,
+    Dump(usize, usize),
+    Poke(usize, Vec<Bit>),
+    Help,
+    Quit,
+}
+
+fn num<T: FromStr>(s: Option<&str>, what: &str) -> Result<T, String> {
+    let s = s.ok_or_else(|| format!("Missing the {}", what))?;
+    s.parse().map_err(|_| format!("Invalid {}: {}", what, s))
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Command::*;
+
+        let mut words = s.split_whitespace();
+        let cmd = match words.next() {
+            Some(c) => c,
+            None => return Err("No command given".to_owned()),
+        };
+
+        let parsed = match cmd {
+            "s" | "step" => Step(match words.next() {
+                Some(n) => num(Some(n), "step count")?,
+                None => 1,
+            }),
+            "bk" | "back" => Back(match words.next() {
+                Some(n) => num(Some(n), "step count")?,
+                None => 1,
+            }),
+            "rw" | "rewind" => Rewind(num(words.next(), "step")?),
+            "c" | "continue" => Continue,
+            "b" | "break" => Break(num(words.next(), "address")?),
+            "bo" | "breakop" => BreakOp(words.next().ok_or("Missing the cmd")?.parse()?),
+            "d" | "delete" => {
+                let w = words.next().ok_or("Missing the address or cmd")?;
+                match w.parse() {
+                    Ok(a) => DeleteAddr(a),
+                    Err(_) => DeleteOp(w.parse()?),
+                }
+            }
+            "w" | "watch" => {
+                let range = words.next().ok_or("Missing the address")?;
+                let addrs = match range.split_once("..") {
+                    Some((start, end)) => num(Some(start), "address")?..=num(Some(end), "address")?,
+                    None => {
+                        let a = num(Some(range), "address")?;
+                        a..=a
+                    }
+                };
+
+                let mut access = Access::ReadWrite;
+                let mut value = words.next();
+                if let Some(Ok(a)) = value.map(str::parse) {
+                    access = a;
+                    value = words.next();
+                }
+
+                Watch(watch::Watch {
+                    addrs,
+                    access,
+                    value: match value {
+                        Some(v) => Some(num(Some(v), "value")?),
+                        None => None,
+                    },
+                })
+            }
+            "uw" | "unwatch" => Unwatch(num(words.next(), "watchpoint number")?),
+            "l" | "list" => List,
+            "r" | "regs" => Regs,
+            "i" | "ins" => Ins,
+            "x" | "dump" => Dump(
+                num(words.next(), "address")?,
+                match words.next() {
+                    Some(n) => num(Some(n), "length")?,
+                    None => 8,
+                },
+            ),
+            "p" | "poke" => {
+                let addr = num(words.next(), "address")?;
+                let vals = words
+                    .by_ref()
+                    .map(|w| num(Some(w), "value"))
+                    .collect::<Result<Vec<Bit>, String>>()?;
+                if vals.is_empty() {
+                    return Err("Missing the values to poke".to_owned());
+                }
+                Poke(addr, vals)
+            }
+            "h" | "help" => Help,
+            "q" | "quit" => Quit,
+            other => return Err(format!("Unknown command: {}", other)),
+        };
+
+        match words.next() {
+            Some(extra) => Err(format!("Unexpected argument: {}", extra)),
+            None => Ok(parsed),
+        }
+    }
+}
+
+pub struct Debugger<'a, 'b, M: Memory = Vec<Bit>> {
+    pub comp: Computer<'a, 'b, Bit, M>,
+    breaks: BTreeSet<usize>,
+    op_breaks: HashSet<Cmd>,
+    halted: bool,
+}
+
+impl<'a, 'b, M: Memory> Debugger<'a, 'b, M> {
+    pub fn new(mut comp: Computer<'a, 'b, Bit, M>) -> Self {
+        comp.set_undo(UNDO_DEPTH);
+
+        Debugger {
+            comp,
+            breaks: BTreeSet::new(),
+            op_breaks: HashSet::new(),
+            halted: false,
+        }
+    }
+
+    fn at_break(&self) -> bool {
+        self.breaks.contains(&self.comp.idx())
+            || self
+                .comp
+                .current()
+                .map(|i| self.op_breaks.contains(&i.cmd()))
+                .unwrap_or(false)
+    }
+
+    /// Runs a single instruction, returning whether the computer can keep going
+    fn step_one(&mut self, out: &mut dyn Write) -> std::io::Result<bool> {
+        if self.halted {
+            writeln!(out, "The computer has halted")?;
+            return Ok(false);
+        }
+
+        match self.comp.step() {
+            Ok(true) => {
+                self.halted = true;
+                writeln!(out, "Halted at {}", self.comp.idx())?;
+                Ok(false)
+            }
+            Ok(false) => match self.comp.watch_hit() {
+                Some(hit) => {
+                    writeln!(out, "Watchpoint: {}", hit)?;
+                    Ok(false)
+                }
+                None => Ok(true),
+            },
+            Err(e) => {
+                writeln!(out, "Error at {}: {}", self.comp.idx(), e)?;
+                Ok(false)
+            }
+        }
+    }
+
+    fn show_ins(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        let idx = self.comp.idx();
+
+        let ins = match self.comp.current() {
+            Ok(i) => i,
+            Err(e) => return writeln!(out, "{:>6}: {}", idx, e),
+        };
+
+        write!(out, "{:>6}: {:<6} {}", idx, ins.raw(), ins.cmd())?;
+        match ins.modes() {
+            Ok(modes) => {
+                for (i, mode) in modes.iter().enumerate() {
+                    let param = self.comp.mem.read(idx + i + 1);
+                    write!(out, " [{} {}]", mode, param)?;
+                }
+                writeln!(out)
+            }
+            Err(e) => writeln!(out, " {}", e),
+        }
+    }
+
+    /// Runs one command; returns false once the user asks to quit
+    pub fn exec(&mut self, cmd: Command, out: &mut dyn Write) -> std::io::Result<bool> {
+        use Command::*;
+
+        match cmd {
+            Step(n) => {
+                for _ in 0..n {
+                    if !self.step_one(out)? {
+                        break;
+                    }
+                }
+                self.show_ins(out)?;
+            }
+
+            Back(n) => {
+                for _ in 0..n {
+                    if !self.comp.step_back() {
+                        writeln!(out, "Nothing left to undo")?;
+                        break;
+                    }
+                    self.halted = false;
+                }
+                self.show_ins(out)?;
+            }
+
+            Rewind(step) => {
+                if self.comp.rewind_to(step) {
+                    self.halted = false;
+                } else {
+                    writeln!(
+                        out,
+                        "Can't rewind to step {}; {} steps have run",
+                        step,
+                        self.comp.steps()
+                    )?;
+                }
+                self.show_ins(out)?;
+            }
+
+            Continue => {
+                // Always move at least once so we don't get stuck on the current breakpoint
+                while self.step_one(out)? {
+                    if self.at_break() {
+                        writeln!(out, "Breakpoint at {}", self.comp.idx())?;
+                        break;
+                    }
+                }
+                self.show_ins(out)?;
+            }
+
+            Break(addr) => {
+                self.breaks.insert(addr);
+            }
+            BreakOp(cmd) => {
+                self.op_breaks.insert(cmd);
+            }
+            DeleteAddr(addr) => {
+                if !self.breaks.remove(&addr) {
+                    writeln!(out, "No breakpoint at {}", addr)?;
+                }
+            }
+            DeleteOp(cmd) => {
+                if !self.op_breaks.remove(&cmd) {
+                    writeln!(out, "No breakpoint on {}", cmd)?;
+                }
+            }
+            Watch(w) => self.comp.add_watch(w),
+            Unwatch(n) => {
+                if self.comp.remove_watch(n).is_none() {
+                    writeln!(out, "No watchpoint {}", n)?;
+                }
+            }
+            List => {
+                for b in &self.breaks {
+                    writeln!(out, "addr {}", b)?;
+                }
+                for c in &self.op_breaks {
+                    writeln!(out, "cmd  {}", c)?;
+                }
+                for (i, w) in self.comp.watches().iter().enumerate() {
+                    writeln!(out, "watch {}: {}", i, w)?;
+                }
+            }
+
+            Regs => writeln!(out, "idx={} rel={}", self.comp.idx(), self.comp.rel())?,
+            Ins => self.show_ins(out)?,
+
+            Dump(addr, len) => {
+                let end = addr.saturating_add(len);
+                for row in (addr..end).step_by(8) {
+                    write!(out, "{:>6}:", row)?;
+                    for a in row..end.min(row.saturating_add(8)) {
+                        write!(out, " {}", self.comp.mem.read(a))?;
+                    }
+                    writeln!(out)?;
+                }
+            }
+
+            Poke(addr, vals) => {
+                for (i, v) in vals.into_iter().enumerate() {
+                    let res = match addr.checked_add(i) {
+                        Some(a) => self.comp.poke(a, v).map_err(|e| e.to_string()),
+                        None => Err(format!("Can't poke past address {}", usize::MAX)),
+                    };
+                    if let Err(e) = res {
+                        writeln!(out, "{}", e)?;
+                        break;
+                    }
+                }
+            }
+
+            Help => writeln!(out, "{}", HELP)?,
+            Quit => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Prompts for and runs commands until one quits or `read_line` runs dry.  Nothing is held
+    /// between reads, so an interactive `Input` can read values from the same stream.
+    pub fn repl(
+        &mut self,
+        mut read_line: impl FnMut(&mut String) -> std::io::Result<usize>,
+        out: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        let mut line = String::new();
+        loop {
+            write!(out, "(idb) ")?;
+            out.flush()?;
+
+            line.clear();
+            if read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match line.parse::<Command>() {
+                Ok(cmd) => {
+                    if !self.exec(cmd, out)? {
+                        return Ok(());
+                    }
+                }
+                Err(e) => writeln!(out, "{}", e)?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::CompError::InputErrStr;
+    use crate::input::FnInput;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    fn run(dbg: &mut Debugger, cmds: &[&str]) -> String {
+        let mut out = vec![];
+        for c in cmds {
+            dbg.exec(c.parse().unwrap(), &mut out).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("s".parse(), Ok(Command::Step(1)));
+        assert_eq!("step 5".parse(), Ok(Command::Step(5)));
+        assert_eq!("bo output".parse(), Ok(Command::BreakOp(Cmd::Output)));
+        assert_eq!("d 4".parse(), Ok(Command::DeleteAddr(4)));
+        assert_eq!(
+            "d 4 5".parse::<Command>(),
+            Err("Unexpected argument: 5".into())
+        );
+        assert_eq!("p 3 1 -2".parse(), Ok(Command::Poke(3, vec![1, -2])));
+        assert!("b".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn breakpoints() {
+        let mut cin = VecDeque::from(vec![4]);
+        let mut cout = vec![];
+        let comp = Computer::new(
+            vec![3, 11, 1002, 11, 2, 11, 4, 11, 99, 0, 0, 0],
+            &mut cin,
+            &mut cout,
+        );
+        let mut dbg = Debugger::new(comp);
+
+        assert_eq!(
+            run(&mut dbg, &["bo output", "c", "r", "x 8 4"]),
+            "Breakpoint at 6\n     6: 4      Output [Position 11]\nidx=6 rel=0\n     8: 99 0 0 8\n"
+        );
+
+        assert_eq!(
+            run(&mut dbg, &["p 11 21", "c", "s"]),
+            "Halted at 8\n     8: 99     Halt\nThe computer has halted\n     8: 99     Halt\n"
+        );
+
+        drop(dbg);
+        assert_eq!(cout, vec![21]);
+    }
+
+    #[test]
+    fn back() {
+        let mut cin = VecDeque::from(vec![4]);
+        let mut cout = vec![];
+        let comp = Computer::new(
+            vec![3, 11, 1002, 11, 2, 11, 4, 11, 99, 0, 0, 0],
+            &mut cin,
+            &mut cout,
+        );
+        let mut dbg = Debugger::new(comp);
+
+        assert_eq!(
+            run(&mut dbg, &["c", "bk 2", "x 11 1", "rw 0", "bk"]),
+            "Halted at 8\n     8: 99     Halt\n\
+             \x20    6: 4      Output [Position 11]\n    11: 8\n\
+             \x20    0: 3      Input [Position 11]\n\
+             Nothing left to undo\n     0: 3      Input [Position 11]\n"
+        );
+    }
+
+    #[test]
+    fn shared_stdin() {
+        // Commands and input values interleaved the way they'd be typed in
+        let lines = Arc::new(Mutex::new(VecDeque::from(vec![
+            "s\n", "4\n", "bogus\n", "\n", "s\n", "x 11 1\n", "q\n", "s\n",
+        ])));
+        fn next(lines: &Mutex<VecDeque<&'static str>>) -> Option<&'static str> {
+            lines.lock().unwrap().pop_front()
+        }
+
+        let values = Arc::clone(&lines);
+        let mut cin = FnInput(move || {
+            let line = next(&values).ok_or(InputErrStr("Out of lines"))?;
+            Ok(line.trim().parse().unwrap())
+        });
+        let mut cout = vec![];
+        let comp = Computer::new(
+            vec![3, 11, 1002, 11, 2, 11, 4, 11, 99, 0, 0, 0],
+            &mut cin,
+            &mut cout,
+        );
+        let mut dbg = Debugger::new(comp);
+
+        let mut out = vec![];
+        dbg.repl(
+            |buf| {
+                let line = next(&lines).unwrap_or("");
+                buf.push_str(line);
+                Ok(line.len())
+            },
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                "(idb)      2: 1002   Multiply [Position 11] [Immediate 2] [Position 11]\n",
+                "(idb) Unknown command: bogus\n",
+                "(idb) (idb)      6: 4      Output [Position 11]\n",
+                "(idb)     11: 8\n",
+                "(idb) "
+            )
+        );
+        assert_eq!(lines.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn poke() {
+        let mut cin = VecDeque::from(vec![4]);
+        let mut cout = vec![];
+        let mut comp = Computer::new(
+            vec![3, 11, 1002, 11, 2, 11, 4, 11, 99, 0, 0, 0],
+            &mut cin,
+            &mut cout,
+        );
+        comp.set_limits(crate::computer::Limits {
+            max_addr: Some((1 << 16) - 1),
+            ..Default::default()
+        });
+        let mut dbg = Debugger::new(comp);
+
+        // Each value poked is undone on its own
+        assert_eq!(
+            run(
+                &mut dbg,
+                &["p 10 5 6", "x 10 2", "bk", "x 10 2", "bk", "x 10 2"]
+            ),
+            concat!(
+                "    10: 5 6\n",
+                "     0: 3      Input [Position 11]\n",
+                "    10: 5 0\n",
+                "     0: 3      Input [Position 11]\n",
+                "    10: 0 0\n"
+            )
+        );
+
+        assert_eq!(
+            run(
+                &mut dbg,
+                &["x 18446744073709551614 9", "p 18446744073709551615 1 2"]
+            ),
+            concat!(
+                "18446744073709551614: 0\n",
+                "The write to 18446744073709551615 at idx 0 is past the highest address allowed, 65535\n"
+            )
+        );
+    }
+
+    #[test]
+    fn poke_without_limits() {
+        let mut cin = VecDeque::new();
+        let mut cout = vec![];
+        let comp = Computer::new(vec![99], &mut cin, &mut cout);
+        let mut dbg = Debugger::new(comp);
+
+        // Neither fits in memory, so they fail rather than taking the debugger down
+        assert_eq!(
+            run(
+                &mut dbg,
+                &[
+                    "p 18446744073709551615 1",
+                    "p 4611686018427387904 1",
+                    "p 2 7",
+                    "x 0 4",
+                    "bk",
+                    "bk"
+                ]
+            ),
+            concat!(
+                "Memory can't grow to fit address 18446744073709551615\n",
+                "Memory can't grow to fit address 4611686018427387904\n",
+                "     0: 99 0 7 0\n",
+                "     0: 99     Halt\n",
+                "Nothing left to undo\n     0: 99     Halt\n"
+            )
+        );
+    }
+
+    #[test]
+    fn watchpoints() {
+        assert_eq!(
+            "w 3..5 w -1".parse(),
+            Ok(Command::Watch(watch::Watch {
+                addrs: 3..=5,
+                access: Access::Write,
+                value: Some(-1),
+            }))
+        );
+        assert!("w 3 x".parse::<Command>().is_err());
+
+        let mut cin = VecDeque::from(vec![4]);
+        let mut cout = vec![];
+        let comp = Computer::new(
+            vec![3, 11, 1002, 11, 2, 11, 4, 11, 99, 0, 0, 0],
+            &mut cin,
+            &mut cout,
+        );
+        let mut dbg = Debugger::new(comp);
+
+        assert_eq!(
+            run(&mut dbg, &["w 11 w 8", "l", "c", "uw 0", "c"]),
+            "watch 0: 11 w =8\n\
+             Watchpoint: [11] written as 8 by Multiply at 2\n     6: 4      Output [Position 11]\n\
+             Halted at 8\n     8: 99     Halt\n"
+        );
+    }
+}
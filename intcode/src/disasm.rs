@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+use crate::computer::{Cmd, Instruction, Mode};
+use crate::Bit;
+
+/// How many data words get grouped onto a single listing line
+const DATA_WIDTH: usize = 8;
+
+/// A parameter rendered per its mode: `[12]` for position, `#5` for immediate and `rel+3` for
+/// relative
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct Operand(pub Mode, pub Bit);
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Mode::Position => write!(f, "[{}]", self.1),
+            Mode::Immediate => write!(f, "#{}", self.1),
+            Mode::Relative => write!(f, "rel{:+}", self.1),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub enum Line {
+    Code {
+        addr: usize,
+        ins: Instruction,
+        operands: Vec<Operand>,
+    },
+    Data {
+        addr: usize,
+        values: Vec<Bit>,
+    },
+}
+
+impl Line {
+    pub fn addr(&self) -> usize {
+        match self {
+            Line::Code { addr, .. } | Line::Data { addr, .. } => *addr,
+        }
+    }
+
+    pub fn raw(&self) -> Vec<Bit> {
+        match self {
+            Line::Code { ins, operands, .. } => std::iter::once(Bit::from(ins.raw()))
+                .chain(operands.iter().map(|o| o.1))
+                .collect(),
+            Line::Data { values, .. } => values.clone(),
+        }
+    }
+}
+
+fn join(bits: &[Bit], sep: &str) -> String {
+    bits.iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let asm = match self {
+            Line::Code { ins, operands, .. } => {
+                let ops = operands
+                    .iter()
+                    .map(|o| o.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{:<9} {}", ins.cmd().mnemonic(), ops)
+            }
+            Line::Data { values, .. } => format!("{:<9} {}", "data", join(values, ", ")),
+        };
+
+        write!(
+            f,
+            "{:<40} ; {:>5}: {}",
+            asm.trim_end(),
+            self.addr(),
+            join(&self.raw(), " ")
+        )
+    }
+}
+
+/// Decodes the instruction at `addr` if it is one that can be written back out exactly
+fn decode(mem: &[Bit], addr: usize) -> Option<(Instruction, Vec<Operand>)> {
+    let ins = Instruction::try_from(*mem.get(addr)?).ok()?;
+    let modes = ins.modes().ok()?;
+    let params = mem.get(addr + 1..addr + 1 + modes.len())?;
+
+    // Stray mode digits past the cmd's arity can't be expressed in a listing, so treat the word
+    // as data to keep the listing faithful to memory
+    if encode(ins.cmd(), &modes) != ins.raw() {
+        return None;
+    }
+
+    // Nor can an immediate mode write, the assembler won't take it
+    if modes
+        .iter()
+        .enumerate()
+        .any(|(i, m)| *m == Mode::Immediate && ins.cmd().writes(i))
+    {
+        return None;
+    }
+
+    let operands = modes
+        .into_iter()
+        .zip(params.iter())
+        .map(|(m, p)| Operand(m, *p))
+        .collect();
+
+    Some((ins, operands))
+}
+
+/// The opcode with the mode digits for each parameter
+pub fn encode(cmd: Cmd, modes: &[Mode]) -> u16 {
+    modes
+        .iter()
+        .zip(&[100, 1_000, 10_000])
+        .fold(cmd.opcode(), |raw, (mode, place)| {
+            raw + place
+                * match mode {
+                    Mode::Position => 0,
+                    Mode::Immediate => 1,
+                    Mode::Relative => 2,
+                }
+        })
+}
+
+/// Addresses execution can move to after the instruction, as far as we can tell statically
+pub(crate) fn successors(addr: usize, ins: Instruction, operands: &[Operand]) -> Vec<usize> {
+    let next = addr + 1 + operands.len();
+
+    match ins.cmd() {
+        Cmd::Halt => vec![],
+
+        Cmd::JumpTrue | Cmd::JumpFalse => {
+            let want_jump = ins.cmd() == Cmd::JumpTrue;
+            let mut succ = vec![];
+
+            // A constant condition means only one of the two branches can ever be taken
+            let cond = operands[0];
+            let always = cond.0 == Mode::Immediate && (cond.1 != 0) == want_jump;
+            let never = cond.0 == Mode::Immediate && (cond.1 != 0) != want_jump;
+
+            if !always {
+                succ.push(next);
+            }
+
+            let target = operands[1];
+            if !never && target.0 == Mode::Immediate {
+                if let Ok(t) = usize::try_from(target.1) {
+                    succ.push(t);
+                }
+            }
+
+            succ
+        }
+
+        _ => vec![next],
+    }
+}
+
+/// Finds the start of every instruction reachable from address 0 by following fall-throughs
+/// and immediate jump targets
+pub fn reachable(mem: &[Bit]) -> BTreeMap<usize, (Instruction, Vec<Operand>)> {
+    let mut code = BTreeMap::new();
+    let mut seen = BTreeSet::new();
+    let mut todo = vec![0];
+
+    while let Some(addr) = todo.pop() {
+        if !seen.insert(addr) {
+            continue;
+        }
+
+        if let Some((ins, operands)) = decode(mem, addr) {
+            todo.extend(successors(addr, ins, &operands));
+            code.insert(addr, (ins, operands));
+        }
+    }
+
+    code
+}
+
+pub fn disassemble(mem: &[Bit]) -> Vec<Line> {
+    let code = reachable(mem);
+    let mut lines = vec![];
+    let mut data: Vec<Bit> = vec![];
+    let mut data_start = 0;
+
+    let mut addr = 0;
+    while addr < mem.len() {
+        // A jump into the middle of an instruction we've already listed can't be shown linearly,
+        // so anything overlapping the previous instruction just stays part of it
+        match code.get(&addr) {
+            Some((ins, operands)) => {
+                if !data.is_empty() {
+                    lines.push(Line::Data {
+                        addr: data_start,
+                        values: data.split_off(0),
+                    });
+                }
+
+                lines.push(Line::Code {
+                    addr,
+                    ins: *ins,
+                    operands: operands.clone(),
+                });
+                addr += 1 + operands.len();
+            }
+
+            None => {
+                if data.is_empty() {
+                    data_start = addr;
+                }
+                data.push(mem[addr]);
+                addr += 1;
+
+                if data.len() == DATA_WIDTH || code.contains_key(&addr) {
+                    lines.push(Line::Data {
+                        addr: data_start,
+                        values: data.split_off(0),
+                    });
+                }
+            }
+        }
+    }
+
+    if !data.is_empty() {
+        lines.push(Line::Data {
+            addr: data_start,
+            values: data,
+        });
+    }
+
+    lines
+}
+
+pub fn listing(mem: &[Bit]) -> String {
+    disassemble(mem)
+        .iter()
+        .map(|l| format!("{}\n", l))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn operands() {
+        assert_eq!(Operand(Mode::Position, 12).to_string(), "[12]");
+        assert_eq!(Operand(Mode::Immediate, -5).to_string(), "#-5");
+        assert_eq!(Operand(Mode::Relative, 3).to_string(), "rel+3");
+        assert_eq!(Operand(Mode::Relative, -1).to_string(), "rel-1");
+    }
+
+    #[test]
+    fn code_and_data() {
+        let mem = vec![1105, 1, 7, 5, 6, 1106, 0, 4, 99, 3, 204, -1];
+        let lines = disassemble(&mem);
+
+        assert_eq!(
+            lines.iter().map(|l| l.to_string()).collect::<Vec<_>>(),
+            vec![
+                "jumptrue  #1, #7                         ;     0: 1105 1 7",
+                "data      5, 6, 1106, 0                  ;     3: 5 6 1106 0",
+                "output    [99]                           ;     7: 4 99",
+                "input     [204]                          ;     9: 3 204",
+                "data      -1                             ;    11: -1",
+            ]
+        );
+
+        let raw: Vec<Bit> = lines.iter().flat_map(|l| l.raw()).collect();
+        assert_eq!(raw, mem);
+    }
+
+    #[test]
+    fn non_canonical() {
+        let lines = disassemble(&[1104, 7, 99]);
+        assert_eq!(
+            lines,
+            vec![Line::Data {
+                addr: 0,
+                values: vec![1104, 7, 99]
+            }]
+        );
+    }
+}
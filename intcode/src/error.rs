@@ -1,16 +1,17 @@
 use std::error::Error;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::path::PathBuf;
 
 use crate::computer::{Cmd, Mode};
 use crate::Bit;
 
+/// Anything that can go wrong; `W` is the machine word of the computer that hit it
 #[derive(Debug)]
-pub enum CompError {
-    AddrOverflow(Cmd, Bit, usize, usize),
-    InvalidAddress(usize, Option<Bit>, Mode, Cmd),
+pub enum CompError<W = Bit> {
+    AddrOverflow(Cmd, W, usize, usize),
+    InvalidAddress(usize, Option<W>, Mode, Cmd),
     InvalidIndex(usize),
-    InvalidInstruction(Bit),
+    InvalidInstruction(W),
     InvalidMode(u16, u8, u16),
     InvalidOutputMode(usize, Cmd),
     InputErr(Box<dyn std::error::Error>),
@@ -19,11 +20,24 @@ pub enum CompError {
     OutputErrStr(&'static str),
     InvalidCsvError(csv::Error, PathBuf),
     InvalidBitStr(String, PathBuf),
+    SnapshotErr(std::io::Error),
+    InvalidSnapshot(usize, String),
+    AsmErr(usize, usize, String),
+    TraceErr(std::io::Error),
+    StepLimit(usize, usize),
+    MemLimit(usize, usize, usize),
+    MemGrow(usize),
+    Timeout(usize),
+    ArithOverflow(usize, Cmd, W, W),
+    Unresolved(usize, String),
+    RecordErr(std::io::Error),
+    InvalidRecord(usize, String),
+    ReplayDiverged(String, (usize, usize), (usize, usize)),
 }
 
-pub type Result<T> = std::result::Result<T, CompError>;
+pub type Result<T, W = Bit> = std::result::Result<T, CompError<W>>;
 
-impl Display for CompError {
+impl<W: Debug + Display> Display for CompError<W> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         use CompError::*;
 
@@ -74,8 +88,57 @@ impl Display for CompError {
                 s,
                 path.display()
             )),
+
+            SnapshotErr(e) => f.write_fmt(format_args!(
+                "There was an issue reading or writing a snapshot: {}",
+                e
+            )),
+            InvalidSnapshot(line, msg) => {
+                f.write_fmt(format_args!("Invalid snapshot on line {}: {}", line, msg))
+            }
+
+            AsmErr(line, col, msg) => {
+                f.write_fmt(format_args!("Assembly error at {}:{}: {}", line, col, msg))
+            }
+
+            TraceErr(e) => f.write_fmt(format_args!("There was an issue writing the trace: {}", e)),
+
+            StepLimit(idx, max) => f.write_fmt(format_args!(
+                "Hit the limit of {} steps at idx {}",
+                max, idx
+            )),
+            MemLimit(idx, addr, max) => f.write_fmt(format_args!(
+                "The write to {} at idx {} is past the highest address allowed, {}",
+                addr, idx, max
+            )),
+            MemGrow(addr) => {
+                f.write_fmt(format_args!("Memory can't grow to fit address {}", addr))
+            }
+            Timeout(idx) => f.write_fmt(format_args!("Ran past the deadline at idx {}", idx)),
+
+            ArithOverflow(idx, cmd, a, b) => f.write_fmt(format_args!(
+                "The cmd {} at idx {} overflowed with the operands {} and {}",
+                cmd, idx, a, b
+            )),
+
+            Unresolved(idx, expr) => f.write_fmt(format_args!(
+                "Couldn't pick a value for {} at idx {}",
+                expr, idx
+            )),
+
+            RecordErr(e) => f.write_fmt(format_args!(
+                "There was an issue reading or writing a recording: {}",
+                e
+            )),
+            InvalidRecord(line, msg) => {
+                f.write_fmt(format_args!("Invalid recording on line {}: {}", line, msg))
+            }
+            ReplayDiverged(tag, (step, addr), (now_step, now_addr)) => f.write_fmt(format_args!(
+                "The replay of {} diverged: the input was recorded at step {} idx {} but asked for at step {} idx {}",
+                tag, step, addr, now_step, now_addr
+            )),
         }
     }
 }
 
-impl Error for CompError {}
+impl<W: Debug + Display> Error for CompError<W> {}
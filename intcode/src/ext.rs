@@ -0,0 +1,54 @@
+//! Extra instructions registered with [`Computer::add_op`] and the trap for unknown opcodes set
+//! with [`Computer::set_trap`].
+//!
+//! Handlers only get to read memory; an instruction writes through its [`Param::Out`] so
+//! watchpoints, the undo journal and the decode cache all see the write like any other.
+//!
+//! [`Computer::add_op`]: crate::computer::Computer::add_op
+//! [`Computer::set_trap`]: crate::computer::Computer::set_trap
+
+use crate::computer::Kind;
+use crate::error::Result;
+use crate::input::Input;
+use crate::memory::Memory;
+use crate::output::Output;
+use crate::word::Word;
+use crate::Bit;
+
+/// How an extra instruction uses one of its params
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Param {
+    /// Read like any other operand, in whatever mode the instruction says
+    In,
+    /// Written with [`Ext::out`]; it can't be immediate and has to be the last param
+    Out,
+}
+
+/// Everything a handler can see and do
+pub struct Ext<'c, W = Bit> {
+    /// Where the instruction starts
+    pub at: usize,
+    /// The instruction word itself, modes and all
+    pub op: W,
+    /// The values of the [`Param::In`] params in order; always empty for a trap
+    pub args: Vec<W>,
+    pub rel: W,
+    pub mem: &'c dyn Memory<W>,
+    pub input: &'c mut dyn Input<W>,
+    pub output: &'c mut dyn Output<W>,
+    /// Written to the [`Param::Out`] param, which is left alone if this is `None`
+    pub out: Option<W>,
+    /// Where to carry on instead of the next instruction.  A trap doesn't know how many params
+    /// the unknown instruction has so without this it carries on with the very next word.
+    pub jump: Option<usize>,
+}
+
+pub type Handler<'a, W = Bit> = dyn FnMut(&mut Ext<'_, W>) -> Result<(), W> + 'a;
+
+/// What an [`OwnedComputer`](crate::computer::OwnedComputer) holds on to instead
+pub type SendHandler<'a, W = Bit> = dyn FnMut(&mut Ext<'_, W>) -> Result<(), W> + Send + 'a;
+
+pub(crate) struct UserOp<'a, W: Word, K: Kind<W>> {
+    pub(crate) params: Vec<Param>,
+    pub(crate) handler: Box<K::Handler<'a>>,
+}
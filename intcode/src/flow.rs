@@ -0,0 +1,176 @@
+//! A static control flow graph, built from what [`disasm::reachable`] can find.
+//!
+//! Jumps whose target comes from memory (position or relative mode) can't be followed without
+//! running the program, so the blocks ending in one are flagged as unresolved and whatever they
+//! jump to is missing from the graph.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::computer::{Cmd, Instruction, Mode};
+use crate::disasm::{reachable, successors, Operand};
+use crate::Bit;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Edge {
+    /// Falling through to the next instruction, including a jump that isn't taken
+    Next,
+    Jump,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Block {
+    pub start: usize,
+    pub code: Vec<(usize, Instruction, Vec<Operand>)>,
+    pub succs: Vec<(usize, Edge)>,
+    /// Ends in a jump to a computed address
+    pub unresolved: bool,
+}
+
+impl Block {
+    /// One past the last word of the block
+    pub fn end(&self) -> usize {
+        self.code
+            .last()
+            .map_or(self.start, |(addr, _, ops)| addr + 1 + ops.len())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Graph {
+    pub blocks: BTreeMap<usize, Block>,
+}
+
+fn is_jump(cmd: Cmd) -> bool {
+    cmd == Cmd::JumpTrue || cmd == Cmd::JumpFalse
+}
+
+pub fn graph(mem: &[Bit]) -> Graph {
+    let code = reachable(mem);
+
+    // Every block starts at the program start, a jump target, or right after a jump or halt
+    let mut leaders = BTreeSet::new();
+    leaders.insert(0);
+    for (addr, (ins, ops)) in &code {
+        if is_jump(ins.cmd()) || ins.cmd() == Cmd::Halt {
+            leaders.extend(successors(*addr, *ins, ops));
+            leaders.insert(addr + 1 + ops.len());
+        }
+    }
+
+    let mut blocks: BTreeMap<usize, Block> = BTreeMap::new();
+    let mut cur: Option<Block> = None;
+
+    for (addr, (ins, ops)) in code {
+        // Anything not contiguous with the current block, say after some data, starts a new one
+        if let Some(mut b) = cur.take() {
+            if leaders.contains(&addr) || b.end() != addr {
+                if b.end() == addr {
+                    b.succs.push((addr, Edge::Next));
+                }
+                blocks.insert(b.start, b);
+            } else {
+                cur = Some(b);
+            }
+        }
+
+        let block = cur.get_or_insert_with(|| Block {
+            start: addr,
+            code: vec![],
+            succs: vec![],
+            unresolved: false,
+        });
+
+        let next = addr + 1 + ops.len();
+        let cmd = ins.cmd();
+        if is_jump(cmd) || cmd == Cmd::Halt {
+            block.succs = successors(addr, ins, &ops)
+                .into_iter()
+                .map(|s| (s, if s == next { Edge::Next } else { Edge::Jump }))
+                .collect();
+            block.unresolved = is_jump(cmd) && ops[1].0 != Mode::Immediate;
+            block.code.push((addr, ins, ops));
+            let b = cur.take().unwrap();
+            blocks.insert(b.start, b);
+        } else {
+            block.code.push((addr, ins, ops));
+        }
+    }
+
+    if let Some(b) = cur {
+        blocks.insert(b.start, b);
+    }
+
+    Graph { blocks }
+}
+
+impl Graph {
+    /// Renders the graph for Graphviz; unresolved blocks are drawn in red
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph intcode {\n    node [shape=box fontname=monospace];\n".to_owned();
+
+        for b in self.blocks.values() {
+            let mut label = String::new();
+            for (addr, ins, ops) in &b.code {
+                let ops = ops
+                    .iter()
+                    .map(|o| o.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let asm = format!("{} {}", ins.cmd().mnemonic(), ops);
+                write!(label, "{}: {}\\l", addr, asm.trim_end()).unwrap();
+            }
+
+            let style = if b.unresolved { " color=red" } else { "" };
+            writeln!(dot, "    b{} [label=\"{}\"{}];", b.start, label, style).unwrap();
+
+            for (to, edge) in &b.succs {
+                let style = match edge {
+                    Edge::Next => " [style=dashed]",
+                    Edge::Jump => "",
+                };
+                writeln!(dot, "    b{} -> b{}{};", b.start, to, style).unwrap();
+            }
+        }
+
+        dot + "}\n"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn blocks() {
+        let mem = vec![
+            3, 20, 1005, 20, 9, 4, 20, 99, 0, 1001, 20, -1, 20, 106, 0, 21, 99, 0, 0, 0, 0, 2,
+        ];
+        let g = graph(&mem);
+
+        let summary: Vec<_> = g
+            .blocks
+            .values()
+            .map(|b| (b.start, b.end(), b.succs.clone(), b.unresolved))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 5, vec![(5, Edge::Next), (9, Edge::Jump)], false),
+                (5, 8, vec![], false),
+                (9, 16, vec![], true),
+            ]
+        );
+
+        assert_eq!(
+            g.to_dot(),
+            "digraph intcode {\n    node [shape=box fontname=monospace];\n\
+             \x20   b0 [label=\"0: input [20]\\l2: jumptrue [20], #9\\l\"];\n\
+             \x20   b0 -> b5 [style=dashed];\n\
+             \x20   b0 -> b9;\n\
+             \x20   b5 [label=\"5: output [20]\\l7: halt\\l\"];\n\
+             \x20   b9 [label=\"9: add [20], #-1, [20]\\l13: jumpfalse #0, [21]\\l\" color=red];\n\
+             }\n"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Differential fuzzing: random well-formed programs have to leave the same memory and outputs on
+//! every interpreter backend.
+//!
+//! The first backend is the reference, [`Computer`] as plain as it gets. Whenever a case makes
+//! them disagree it is shrunk down to something small enough to read before it's reported.
+
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::computer::{Cmd, Computer, DecodeCache, Limits, Mode};
+use crate::memory::{Memory, Paged};
+use crate::symbolic::Symbolic;
+use crate::Bit;
+
+/// A program and everything it will be fed
+#[derive(Debug, Default, Eq, PartialEq, Clone, Hash)]
+pub struct Case {
+    pub mem: Vec<Bit>,
+    pub input: Vec<Bit>,
+}
+
+/// The final memory and outputs, or why the run failed
+pub type Outcome = std::result::Result<(Vec<Bit>, Vec<Bit>), String>;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Backend {
+    pub name: &'static str,
+    pub run: fn(&Case) -> Outcome,
+}
+
+/// Every backend is held to these so loops and huge writes fail the same way everywhere
+pub const LIMITS: Limits = Limits {
+    max_steps: Some(10_000),
+    max_addr: Some((1 << 16) - 1),
+    deadline: None,
+};
+
+fn computer<M: Memory>(mem: M, case: &Case, cache: bool) -> Outcome {
+    let mut cin: VecDeque<Bit> = case.input.iter().copied().collect();
+    let mut cout = vec![];
+
+    let mut c = Computer::with_memory(mem, &mut cin, &mut cout);
+    c.set_limits(LIMITS);
+    if cache {
+        c.set_cache(Some(DecodeCache::new(&case.mem)));
+    }
+
+    let res = c.run();
+    let mem = (0..c.mem.len()).map(|a| c.mem.read(a)).collect();
+    drop(c);
+
+    res.map(|_| (mem, cout)).map_err(|e| e.to_string())
+}
+
+fn symbolic(case: &Case) -> Outcome {
+    let mut s = Symbolic::new(&case.mem);
+    s.set_limits(LIMITS);
+    for i in &case.input {
+        s.feed(std::rc::Rc::new(crate::symbolic::Expr::Const(*i)));
+    }
+    s.run().map_err(|e| e.to_string())?;
+
+    // With nothing unknown going in everything coming out has to be constant
+    let konst = |vs: &[crate::symbolic::Sym]| {
+        vs.iter()
+            .map(|v| v.konst())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "Ran out of input".to_owned())
+    };
+    Ok((konst(s.mem())?, konst(s.outputs())?))
+}
+
+/// Every backend in the crate, the reference first
+pub fn backends() -> Vec<Backend> {
+    vec![
+        Backend {
+            name: "computer",
+            run: |case| computer(case.mem.clone(), case, false),
+        },
+        Backend {
+            name: "cached",
+            run: |case| computer(case.mem.clone(), case, true),
+        },
+        Backend {
+            name: "paged",
+            run: |case| computer(Paged::from(case.mem.clone()), case, false),
+        },
+        Backend {
+            name: "symbolic",
+            run: symbolic,
+        },
+    ]
+}
+
+/// How to build random programs
+#[derive(Debug, Clone)]
+pub struct Gen {
+    /// What the programs are made of; a halt is always added at the end
+    pub cmds: Vec<Cmd>,
+    /// How many instructions come before the halt
+    pub size: usize,
+    /// How many words of data follow the code
+    pub data: usize,
+    /// Position mode is all that's used without this
+    pub all_modes: bool,
+    /// Let the odd instruction write over the code
+    pub self_modify: bool,
+    /// Where the data, inputs and immediates are picked from
+    pub values: RangeInclusive<Bit>,
+}
+
+impl Default for Gen {
+    fn default() -> Self {
+        use Cmd::*;
+
+        Gen {
+            cmds: vec![
+                Add, Multiply, Input, Output, JumpTrue, JumpFalse, LessThan, Equals, AdjustRel,
+            ],
+            size: 12,
+            data: 8,
+            all_modes: true,
+            self_modify: true,
+            values: -20..=20,
+        }
+    }
+}
+
+impl Gen {
+    pub fn generate(&self, rng: &mut impl Rng) -> Case {
+        let cmds: Vec<Cmd> = (0..self.size)
+            .filter_map(|_| self.cmds.choose(rng).copied())
+            .collect();
+
+        let mut starts = vec![0];
+        for cmd in &cmds {
+            starts.push(starts.last().unwrap() + 1 + cmd.arity());
+        }
+        let code = starts.last().unwrap() + 1;
+        let len = code + self.data;
+
+        let mut case = Case::default();
+        for (i, cmd) in cmds.iter().enumerate() {
+            let mut op = cmd.opcode() as Bit;
+            let mut params = vec![];
+
+            for (p, place) in [100, 1_000, 10_000].iter().enumerate().take(cmd.arity()) {
+                let jump = p == 1 && (*cmd == Cmd::JumpTrue || *cmd == Cmd::JumpFalse);
+                let mode = if jump {
+                    Mode::Immediate
+                } else if !self.all_modes {
+                    Mode::Position
+                } else if cmd.writes(p) {
+                    *[Mode::Position, Mode::Relative].choose(rng).unwrap()
+                } else {
+                    *[Mode::Position, Mode::Immediate, Mode::Relative]
+                        .choose(rng)
+                        .unwrap()
+                };
+
+                op += place
+                    * match mode {
+                        Mode::Position => 0,
+                        Mode::Immediate => 1,
+                        Mode::Relative => 2,
+                    };
+
+                params.push(match mode {
+                    // Only ever jump forwards to the start of an instruction, so the programs
+                    // that don't rewrite themselves always finish
+                    _ if jump => *starts[i + 1..].choose(rng).unwrap() as Bit,
+                    Mode::Immediate => rng.gen_range(self.values.start(), self.values.end() + 1),
+                    // Most writes go to the data, otherwise nearly every program trashes itself
+                    _ if cmd.writes(p) && !(self.self_modify && rng.gen_ratio(1, 8)) => {
+                        rng.gen_range(code, len) as Bit
+                    }
+                    _ => rng.gen_range(0, len) as Bit,
+                });
+            }
+
+            case.mem.push(op);
+            case.mem.extend(params);
+        }
+
+        case.mem.push(Cmd::Halt.opcode() as Bit);
+        for _ in 0..self.data {
+            case.mem
+                .push(rng.gen_range(self.values.start(), self.values.end() + 1));
+        }
+
+        for _ in cmds.iter().filter(|c| **c == Cmd::Input) {
+            case.input
+                .push(rng.gen_range(self.values.start(), self.values.end() + 1));
+        }
+
+        case
+    }
+}
+
+/// Growing memory only to write zeros doesn't count as a difference
+fn trimmed(mem: &[Bit]) -> &[Bit] {
+    let end = mem.iter().rposition(|v| *v != 0).map_or(0, |i| i + 1);
+    &mem[..end]
+}
+
+/// Failures only have to agree that there was one, every backend words its errors differently
+fn same(a: &Outcome, b: &Outcome) -> bool {
+    match (a, b) {
+        (Ok((ma, oa)), Ok((mb, ob))) => trimmed(ma) == trimmed(mb) && oa == ob,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub case: Case,
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let join = |vs: &[Bit]| {
+            vs.iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        writeln!(f, "program: {}", join(&self.case.mem))?;
+        writeln!(f, "input:   {}", join(&self.case.input))?;
+        for (name, outcome) in &self.outcomes {
+            match outcome {
+                Ok((mem, out)) => writeln!(
+                    f,
+                    "  {:<10} mem={} out={}",
+                    name,
+                    join(trimmed(mem)),
+                    join(out)
+                )?,
+                Err(e) => writeln!(f, "  {:<10} error: {}", name, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the case on every backend, returning what they did if any disagrees with the first
+pub fn check(case: &Case, backends: &[Backend]) -> Option<Mismatch> {
+    let outcomes: Vec<_> = backends.iter().map(|b| (b.name, (b.run)(case))).collect();
+
+    if outcomes.iter().all(|(_, o)| same(o, &outcomes[0].1)) {
+        None
+    } else {
+        Some(Mismatch {
+            case: case.clone(),
+            outcomes,
+        })
+    }
+}
+
+/// Everything one step simpler than `case`: a word or input gone, or a value closer to zero
+fn simpler(case: &Case) -> Vec<Case> {
+    let mut cases = vec![];
+
+    for i in 0..case.input.len() {
+        let mut c = case.clone();
+        c.input.remove(i);
+        cases.push(c);
+    }
+
+    for n in (1..=4).rev() {
+        for i in 0..(case.mem.len() + 1).saturating_sub(n) {
+            let mut c = case.clone();
+            c.mem.drain(i..i + n);
+            cases.push(c);
+        }
+    }
+
+    for (i, v) in case.mem.iter().chain(&case.input).enumerate() {
+        for smaller in &[0, v / 2] {
+            if smaller.abs() < v.abs() {
+                let mut c = case.clone();
+                match c.mem.get_mut(i) {
+                    Some(w) => *w = *smaller,
+                    None => c.input[i - case.mem.len()] = *smaller,
+                }
+                cases.push(c);
+            }
+        }
+    }
+
+    cases
+}
+
+/// Keeps simplifying the case for as long as the backends still disagree on it
+pub fn shrink(mut mismatch: Mismatch, backends: &[Backend]) -> Mismatch {
+    while let Some(m) = simpler(&mismatch.case)
+        .iter()
+        .find_map(|c| check(c, backends))
+    {
+        mismatch = m;
+    }
+    mismatch
+}
+
+/// Tries `runs` programs from the seed, returning the first disagreement, shrunk
+pub fn fuzz(gen: &Gen, seed: u64, runs: usize, backends: &[Backend]) -> Option<Mismatch> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (0..runs)
+        .find_map(|_| check(&gen.generate(&mut rng), backends))
+        .map(|m| shrink(m, backends))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backends_agree() {
+        if let Some(m) = fuzz(&Gen::default(), 2019, 500, &backends()) {
+            panic!("The backends disagree\n{}", m);
+        }
+    }
+
+    #[test]
+    fn shrinks() {
+        // Pretend a backend drops the sign of every output
+        let broken = Backend {
+            name: "broken",
+            run: |case| {
+                (backends()[0].run)(case)
+                    .map(|(mem, out)| (mem, out.into_iter().map(Bit::abs).collect()))
+            },
+        };
+
+        let gen = Gen {
+            cmds: vec![Cmd::Add, Cmd::Output],
+            ..Gen::default()
+        };
+        let m = fuzz(&gen, 7, 500, &[backends()[0], broken]).unwrap();
+
+        assert_eq!(m.case.input, vec![]);
+        assert_eq!(m.case.mem, vec![104, -1, 99]);
+        assert_eq!(
+            m.to_string(),
+            "program: 104,-1,99\ninput:   \n  computer   mem=104,-1,99 out=-1\n  broken     mem=104,-1,99 out=1\n"
+        );
+    }
+}
@@ -5,57 +5,122 @@ use crossbeam::Receiver;
 
 use crate::error::CompError::{InputErr, InputErrStr};
 use crate::error::Result;
+use crate::word::Word;
 use crate::Bit;
 
-pub trait Input {
-    fn get_in(&mut self) -> Result<Bit>;
+pub trait Input<W = Bit> {
+    fn get_in(&mut self) -> Result<W, W>;
+
+    /// Told how many steps have run and where the instruction is right before the computer asks
+    /// for a value; only the likes of [`RecordInput`](crate::record::RecordInput) care
+    fn at(&mut self, _steps: usize, _addr: usize) {}
 }
 
-impl Input for VecDeque<Bit> {
-    fn get_in(&mut self) -> Result<Bit> {
+impl<W: Word> Input<W> for VecDeque<W> {
+    fn get_in(&mut self) -> Result<W, W> {
         self.pop_front()
             .ok_or_else(|| InputErrStr("Ran out of elements in the input vector"))
     }
 }
 
-pub struct Single(Bit, bool);
+pub struct Single<W = Bit>(W, bool);
 
-impl Input for Single {
-    fn get_in(&mut self) -> Result<Bit> {
+impl<W: Word> Input<W> for Single<W> {
+    fn get_in(&mut self) -> Result<W, W> {
         if self.1 {
             Err(InputErrStr(""))
         } else {
             self.1 = true;
-            Ok(self.0)
+            Ok(self.0.clone())
         }
     }
 }
 
-impl Single {
-    pub fn new(b: Bit) -> Self {
+impl<W> Single<W> {
+    pub fn new(b: W) -> Self {
         Single(b, false)
     }
 }
 
+/// Asks the closure for every value
+pub struct FnInput<F>(pub F);
+
+impl<W: Word, F: FnMut() -> Result<W, W>> Input<W> for FnInput<F> {
+    fn get_in(&mut self) -> Result<W, W> {
+        (self.0)()
+    }
+}
+
 pub struct Interactive;
 
-impl Input for Interactive {
-    fn get_in(&mut self) -> Result<Bit> {
-        dialoguer::Input::<Bit>::new()
+impl<W: Word> Input<W> for Interactive
+where
+    W::Err: std::fmt::Display + std::fmt::Debug,
+{
+    fn get_in(&mut self) -> Result<W, W> {
+        dialoguer::Input::<W>::new()
             .with_prompt("Next input number")
             .interact()
             .map_err(|e| InputErr(Box::new(e)))
     }
 }
 
-impl Input for Receiver<Bit> {
-    fn get_in(&mut self) -> Result<Bit> {
+/// Feeds text in as character codes, ending every line with a `\n` like the ASCII programs expect
+pub struct AsciiInput<W = Bit>(VecDeque<W>);
+
+impl<W: Word> AsciiInput<W> {
+    /// `\r\n` is turned into `\n` and the last line gets one if it's missing
+    pub fn new(text: &str) -> Self {
+        let mut input = AsciiInput(VecDeque::with_capacity(text.len() + 1));
+        for line in text.lines() {
+            input.push_line(line);
+        }
+        input
+    }
+
+    pub fn lines<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut input = AsciiInput(VecDeque::new());
+        for line in lines {
+            input.push_line(line.as_ref());
+        }
+        input
+    }
+
+    /// Queues up another line after whatever hasn't been read yet
+    pub fn push_line(&mut self, line: &str) {
+        self.0.extend(
+            line.trim_end_matches(&['\r', '\n'][..])
+                .chars()
+                .chain(Some('\n'))
+                .map(|c| W::from(c as i32)),
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<W: Word> Input<W> for AsciiInput<W> {
+    fn get_in(&mut self) -> Result<W, W> {
+        self.0
+            .pop_front()
+            .ok_or_else(|| InputErrStr("Ran out of ASCII input"))
+    }
+}
+
+impl<W: Word> Input<W> for Receiver<W> {
+    fn get_in(&mut self) -> Result<W, W> {
         self.recv().map_err(|e| InputErr(Box::new(e)))
     }
 }
 
-impl Input for BusReader<Bit> {
-    fn get_in(&mut self) -> Result<Bit> {
+impl<W: Word + Sync> Input<W> for BusReader<W> {
+    fn get_in(&mut self) -> Result<W, W> {
         self.recv().map_err(|e| InputErr(Box::new(e)))
     }
 }
@@ -72,6 +137,19 @@ mod test {
         assert_eq!(test.get_in().unwrap(), 3);
     }
 
+    #[test]
+    fn ascii() {
+        let mut input: AsciiInput = AsciiInput::new("ab\r\ncd");
+        let codes: Vec<_> = std::iter::from_fn(|| input.get_in().ok()).collect();
+        assert_eq!(codes, vec![97, 98, 10, 99, 100, 10]);
+
+        let mut input: AsciiInput = AsciiInput::lines(["A", ""]);
+        input.push_line("B\n");
+        let codes: Vec<_> = std::iter::from_fn(|| input.get_in().ok()).collect();
+        assert_eq!(codes, vec![65, 10, 10, 66, 10]);
+        assert!(input.is_empty());
+    }
+
     #[test]
     #[should_panic(expected = "Ran out of elements")]
     fn vec_overflow() {
@@ -0,0 +1,96 @@
+//! Running a computer as an iterator of its outputs, see [`Computer::outputs`].
+//!
+//! [`Computer::outputs`]: crate::computer::Computer::outputs
+
+use crate::computer::{Computer, OwnedComputer, RunState};
+use crate::error::CompError::InputErrStr;
+use crate::error::Result;
+use crate::word::Word;
+use crate::Bit;
+
+/// Runs the computer just far enough to produce each output, pulling inputs as it needs them.
+///
+/// Running out of inputs is an error like it is for a `VecDeque`.  Nothing more comes after an
+/// error or halting.
+pub struct Outputs<I, W: Word + 'static = Bit> {
+    comp: OwnedComputer<'static, W>,
+    input: I,
+    done: bool,
+}
+
+impl<W: Word + 'static, I: Iterator<Item = W>> Outputs<I, W> {
+    pub(crate) fn new(mem: Vec<W>, input: I) -> Self {
+        Outputs {
+            comp: Computer::without_io(mem),
+            input,
+            done: false,
+        }
+    }
+
+    /// Set limits or poke at memory, before or after
+    pub fn computer(&mut self) -> &mut OwnedComputer<'static, W> {
+        &mut self.comp
+    }
+
+    pub fn into_computer(self) -> OwnedComputer<'static, W> {
+        self.comp
+    }
+}
+
+impl<W: Word + 'static, I: Iterator<Item = W>> Iterator for Outputs<I, W> {
+    type Item = Result<W, W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.comp.resume() {
+                Ok(RunState::Output(v)) => return Some(Ok(v)),
+                Ok(RunState::NeedsInput) => match self.input.next() {
+                    Some(v) => self.comp.feed(v),
+                    None => {
+                        self.done = true;
+                        return Some(Err(InputErrStr(
+                            "Ran out of elements in the input iterator",
+                        )));
+                    }
+                },
+                Ok(RunState::Watch(_)) => {}
+                Ok(RunState::Halted) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn outputs() {
+        // Outputs each input doubled until it's handed a zero
+        let mem = vec![
+            3, 15, 1006, 15, 14, 1002, 15, 2, 15, 4, 15, 1105, 1, 0, 99, 0,
+        ];
+
+        let mut outs = Computer::outputs(mem.clone(), vec![1, 2, 3, 0]);
+        let all: Result<Vec<_>> = outs.by_ref().collect();
+        assert_eq!(all.unwrap(), vec![2, 4, 6]);
+        assert_eq!(outs.computer().mem[15], 0);
+
+        // Never asks for more than it needs
+        let doubled: Vec<_> = Computer::outputs(mem.clone(), 1..)
+            .map(Result::unwrap)
+            .take_while(|v| *v < 10)
+            .collect();
+        assert_eq!(doubled, vec![2, 4, 6, 8]);
+
+        let mut outs = Computer::outputs(mem, vec![5]);
+        assert_eq!(outs.next().unwrap().unwrap(), 10);
+        assert!(matches!(outs.next(), Some(Err(InputErrStr(_)))));
+        assert!(outs.next().is_none());
+    }
+}
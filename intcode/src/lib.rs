@@ -2,6 +2,9 @@ use crossbeam::unbounded;
 use crossbeam::Receiver;
 use crossbeam::Sender;
 
+use crate::word::Word;
+
+/// The default word; past 64 bits use `Computer<i128>`, or `Computer<BigInt>` with `bigint` on
 pub type Bit = i64;
 
 pub fn bit_from_bool(b: bool) -> Bit {
@@ -12,10 +15,10 @@ pub fn bit_from_bool(b: bool) -> Bit {
     }
 }
 
-pub fn chan_pair(start_ins: &[Bit]) -> (Receiver<Bit>, Sender<Bit>) {
+pub fn chan_pair<W: Word>(start_ins: &[W]) -> (Receiver<W>, Sender<W>) {
     let (send, recv) = unbounded();
     for i in start_ins {
-        send.send(*i).unwrap();
+        send.send(i.clone()).unwrap();
     }
 
     (recv, send)
@@ -23,6 +26,26 @@ pub fn chan_pair(start_ins: &[Bit]) -> (Receiver<Bit>, Sender<Bit>) {
 
 pub mod error;
 
+pub mod asm;
+#[cfg(feature = "async")]
+pub mod async_io;
 pub mod computer;
+pub mod debugger;
+pub mod disasm;
+pub mod ext;
+pub mod flow;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 pub mod input;
+pub mod iter;
+pub mod memory;
 pub mod output;
+pub mod profile;
+pub mod record;
+pub mod snapshot;
+pub mod symbolic;
+pub mod tcp;
+pub mod trace;
+pub mod transpile;
+pub mod watch;
+pub mod word;
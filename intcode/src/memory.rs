@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crate::error::CompError::MemGrow;
+use crate::error::Result;
+use crate::word::Word;
+use crate::Bit;
+
+/// Backing storage for a [`Computer`](crate::computer::Computer).
+///
+/// Memory is conceptually infinite; cells that were never written read as 0.
+pub trait Memory<W = Bit> {
+    /// One past the highest address that was loaded or written
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read(&self, addr: usize) -> W;
+
+    /// Fails if memory can't grow to fit `addr`
+    fn write(&mut self, addr: usize, val: W) -> Result<(), W>;
+}
+
+/// Plain dense memory; writing past the end grows it to fit
+impl<W: Word> Memory<W> for Vec<W> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn read(&self, addr: usize) -> W {
+        self.get(addr).cloned().unwrap_or_else(|| W::from(0))
+    }
+
+    fn write(&mut self, addr: usize, val: W) -> Result<(), W> {
+        if addr >= Vec::len(self) {
+            let len = addr.checked_add(1).ok_or(MemGrow(addr))?;
+            self.try_reserve(len - Vec::len(self))
+                .map_err(|_| MemGrow(addr))?;
+            self.resize(len, W::from(0));
+        }
+        self[addr] = val;
+        Ok(())
+    }
+}
+
+pub const PAGE_SIZE: usize = 1024;
+
+/// Sparse memory that only allocates the pages that are actually touched, so far away scratch
+/// addresses don't cost anything
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Paged<W = Bit> {
+    pages: HashMap<usize, Box<[W]>>,
+    len: usize,
+}
+
+impl<W: Word> Default for Paged<W> {
+    fn default() -> Self {
+        Paged {
+            pages: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+fn blank_page<W: Word>() -> Box<[W]> {
+    vec![W::from(0); PAGE_SIZE].into_boxed_slice()
+}
+
+impl<W: Word> Paged<W> {
+    pub fn new() -> Self {
+        Paged::default()
+    }
+
+    /// How many pages have been allocated
+    pub fn pages(&self) -> usize {
+        self.pages.len()
+    }
+}
+
+impl<W: Word> From<Vec<W>> for Paged<W> {
+    fn from(mem: Vec<W>) -> Self {
+        let mut paged = Paged::new();
+        for (i, chunk) in mem.chunks(PAGE_SIZE).enumerate() {
+            let mut page = blank_page();
+            page[..chunk.len()].clone_from_slice(chunk);
+            paged.pages.insert(i, page);
+        }
+        paged.len = mem.len();
+        paged
+    }
+}
+
+impl<W: Word> Memory<W> for Paged<W> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn read(&self, addr: usize) -> W {
+        self.pages
+            .get(&(addr / PAGE_SIZE))
+            .map_or_else(|| W::from(0), |p| p[addr % PAGE_SIZE].clone())
+    }
+
+    fn write(&mut self, addr: usize, val: W) -> Result<(), W> {
+        let len = addr.checked_add(1).ok_or(MemGrow(addr))?;
+        let page = self
+            .pages
+            .entry(addr / PAGE_SIZE)
+            .or_insert_with(blank_page);
+        page[addr % PAGE_SIZE] = val;
+
+        self.len = self.len.max(len);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dense() {
+        let mut mem: Vec<Bit> = vec![1, 2];
+        assert_eq!(mem.read(1), 2);
+        assert_eq!(mem.read(5), 0);
+
+        Memory::write(&mut mem, 4, 9).unwrap();
+        assert_eq!(mem, vec![1, 2, 0, 0, 9]);
+
+        // Too big to ever fit, the write fails instead of aborting
+        assert!(Memory::write(&mut mem, usize::MAX, 1).is_err());
+        assert!(Memory::write(&mut mem, 1 << 62, 1).is_err());
+        assert_eq!(mem.len(), 5);
+    }
+
+    #[test]
+    fn paged() {
+        let mut mem: Paged = Paged::from(vec![1, 2, 3]);
+        assert_eq!(mem.len(), 3);
+        assert_eq!(mem.read(2), 3);
+        assert_eq!(mem.pages(), 1);
+
+        mem.write(1 << 40, 7).unwrap();
+        assert!(mem.write(usize::MAX, 7).is_err());
+        assert_eq!(mem.read(1 << 40), 7);
+        assert_eq!(mem.read((1 << 40) + 1), 0);
+        assert_eq!(mem.len(), (1 << 40) + 1);
+        assert_eq!(mem.pages(), 2);
+    }
+}
@@ -1,47 +1,62 @@
 use std::cell::RefCell;
 use std::fmt::Write;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use bus::Bus;
 use crossbeam::Sender;
 
 use crate::error::CompError::OutputErr;
 use crate::error::Result;
+use crate::word::Word;
 use crate::Bit;
 
-pub trait Output {
-    fn put_out(&mut self, n: Bit) -> Result<()>;
+pub trait Output<W = Bit> {
+    fn put_out(&mut self, n: W) -> Result<(), W>;
+
+    /// Like [`Input::at`](crate::input::Input::at), right before a value is put out
+    fn at(&mut self, _steps: usize, _addr: usize) {}
 }
 
-impl Output for Vec<Bit> {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+impl<W: Word> Output<W> for Vec<W> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
         self.push(n);
         Ok(())
     }
 }
 
-impl Output for Rc<RefCell<Vec<Bit>>> {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+impl<W: Word> Output<W> for Rc<RefCell<Vec<W>>> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
         let mut b = self.as_ref().borrow_mut();
         b.push(n);
         Ok(())
     }
 }
 
-impl Output for dyn std::io::Write {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+/// The thread safe take on the `Rc<RefCell<Vec>>`, for an
+/// [`OwnedComputer`](crate::computer::OwnedComputer)
+impl<W: Word> Output<W> for Arc<Mutex<Vec<W>>> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
+        let mut b = self.lock().unwrap_or_else(|e| e.into_inner());
+        b.push(n);
+        Ok(())
+    }
+}
+
+impl<W: Word> Output<W> for dyn std::io::Write {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
         writeln!(self, "{}", n).map_err(|e| OutputErr(Box::new(e)))
     }
 }
 
-impl Output for dyn std::fmt::Write {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+impl<W: Word> Output<W> for dyn std::fmt::Write {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
         writeln!(self, "{}", n).map_err(|e| OutputErr(Box::new(e)))
     }
 }
 
-impl Output for String {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+impl<W: Word> Output<W> for String {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
         self.write_fmt(format_args!(
             "{}{}",
             if self.is_empty() { "" } else { ", " },
@@ -53,21 +68,75 @@ impl Output for String {
 
 pub struct PrintOutput;
 
-impl Output for PrintOutput {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+impl<W: Word> Output<W> for PrintOutput {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
+        println!("{}", n);
+        Ok(())
+    }
+}
+
+/// Where an [`AsciiOutput`] sends what it decodes
+pub trait AsciiSink<W = Bit> {
+    fn text(&mut self, c: char) -> Result<(), W>;
+
+    /// Anything outside of 0–127, usually the answer to the puzzle
+    fn large(&mut self, n: W) -> Result<(), W>;
+}
+
+/// Large values go on a line of their own
+impl<W: Word> AsciiSink<W> for PrintOutput {
+    fn text(&mut self, c: char) -> Result<(), W> {
+        print!("{}", c);
+        Ok(())
+    }
+
+    fn large(&mut self, n: W) -> Result<(), W> {
         println!("{}", n);
         Ok(())
     }
 }
 
-impl Output for Sender<Bit> {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+/// Renders exactly like [`PrintOutput`] does
+impl<W: Word> AsciiSink<W> for String {
+    fn text(&mut self, c: char) -> Result<(), W> {
+        self.push(c);
+        Ok(())
+    }
+
+    fn large(&mut self, n: W) -> Result<(), W> {
+        writeln!(self, "{}", n).map_err(|e| OutputErr(Box::new(e)))
+    }
+}
+
+/// Hands every value to the closure as soon as it's output
+pub struct FnOutput<F>(pub F);
+
+impl<W: Word, F: FnMut(W) -> Result<(), W>> Output<W> for FnOutput<F> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
+        (self.0)(n)
+    }
+}
+
+/// Treats every output as a character code, handing anything that isn't ASCII on separately
+pub struct AsciiOutput<S>(pub S);
+
+impl<W: Word, S: AsciiSink<W>> Output<W> for AsciiOutput<S> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
+        match n.to_usize().filter(|c| *c < 128) {
+            Some(c) => self.0.text(c as u8 as char),
+            None => self.0.large(n),
+        }
+    }
+}
+
+impl<W: Word + Send + 'static> Output<W> for Sender<W> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
         self.send(n).map_err(|e| OutputErr(Box::new(e)))
     }
 }
 
-impl Output for Bus<Bit> {
-    fn put_out(&mut self, n: Bit) -> Result<()> {
+impl<W: Word> Output<W> for Bus<W> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
         self.broadcast(n);
         Ok(())
     }
@@ -90,6 +159,18 @@ mod test {
         assert_eq!(v[2], 3);
     }
 
+    #[test]
+    fn shared_vecs() {
+        let rc = Rc::new(RefCell::new(vec![]));
+        Rc::clone(&rc).put_out(1).unwrap();
+        Rc::clone(&rc).put_out(2).unwrap();
+        assert_eq!(*rc.borrow(), vec![1, 2]);
+
+        let arc = Arc::new(Mutex::new(vec![]));
+        Arc::clone(&arc).put_out(3).unwrap();
+        assert_eq!(*arc.lock().unwrap(), vec![3]);
+    }
+
     #[test]
     fn writer() {
         let mut s = String::with_capacity(50);
@@ -101,4 +182,14 @@ mod test {
 
         assert_eq!(s, "1, 2, 10, 3")
     }
+
+    #[test]
+    fn ascii() {
+        let mut out = AsciiOutput(String::new());
+        for n in &[72, 105, 10, 1_000, -1, 33] {
+            out.put_out(*n).unwrap();
+        }
+
+        assert_eq!(out.0, "Hi\n1000\n-1\n!");
+    }
 }
@@ -0,0 +1,199 @@
+//! Counts where a program spends its time.
+//!
+//! A [`Profile`] is just a [`Tracer`], so hook it up with
+//! [`Computer::set_tracer`](crate::computer::Computer::set_tracer) and read it back once the
+//! program is done.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::computer::Cmd;
+use crate::error::Result;
+use crate::trace::{Event, Tracer};
+
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct Profile {
+    pub steps: usize,
+    /// How many times each cmd ran
+    pub cmds: HashMap<Cmd, usize>,
+    /// How many times the instruction at each address ran
+    pub hits: HashMap<usize, usize>,
+    /// How many times each address was loaded by an operand
+    pub reads: HashMap<usize, usize>,
+    pub writes: HashMap<usize, usize>,
+    pub inputs: usize,
+    pub outputs: usize,
+}
+
+impl<W> Tracer<W> for Profile {
+    fn trace(&mut self, ev: &Event<W>) -> Result<(), W> {
+        self.steps += 1;
+        *self.cmds.entry(ev.cmd).or_insert(0) += 1;
+        *self.hits.entry(ev.addr).or_insert(0) += 1;
+
+        for addr in &ev.reads {
+            *self.reads.entry(*addr).or_insert(0) += 1;
+        }
+        if let Some((addr, _)) = &ev.write {
+            *self.writes.entry(*addr).or_insert(0) += 1;
+        }
+
+        match ev.cmd {
+            Cmd::Input => self.inputs += 1,
+            Cmd::Output => self.outputs += 1,
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+/// Busiest first, ties broken by the lower key
+fn by_count<K: Ord + Copy>(counts: &HashMap<K, usize>) -> Vec<(K, usize)> {
+    let mut sorted: Vec<_> = counts.iter().map(|(k, n)| (*k, *n)).collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    sorted
+}
+
+fn by_key(counts: &HashMap<usize, usize>) -> String {
+    let mut sorted: Vec<_> = counts.iter().collect();
+    sorted.sort();
+    sorted
+        .iter()
+        .map(|(k, n)| format!("[{},{}]", k, n))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl Profile {
+    pub fn new() -> Self {
+        Profile::default()
+    }
+
+    fn pct(&self, n: usize) -> f64 {
+        if self.steps == 0 {
+            0.0
+        } else {
+            n as f64 * 100.0 / self.steps as f64
+        }
+    }
+
+    /// A human readable summary listing every cmd and the `top` busiest addresses of each kind
+    pub fn report(&self, top: usize) -> String {
+        let mut out = format!(
+            "steps={} inputs={} outputs={}\n\n{:<9} {:>12} {:>7}\n",
+            self.steps, self.inputs, self.outputs, "cmd", "count", "%"
+        );
+
+        for (cmd, n) in by_count(&self.cmds) {
+            out += &format!("{:<9} {:>12} {:>6.2}%\n", cmd.mnemonic(), n, self.pct(n));
+        }
+
+        for (title, counts) in &[
+            ("hits", &self.hits),
+            ("reads", &self.reads),
+            ("writes", &self.writes),
+        ] {
+            out += &format!("\n{:<9} {:>12}\n", format!("addr/{}", title), "count");
+            for (addr, n) in by_count(counts).into_iter().take(top) {
+                out += &format!("{:<9} {:>12}\n", addr, n);
+            }
+        }
+
+        out
+    }
+
+    /// Dumps every count as a single JSON object; the address counts are `[addr, count]` pairs
+    /// sorted by address
+    pub fn write_json(&self, w: &mut dyn Write) -> io::Result<()> {
+        let mut cmds: Vec<_> = self.cmds.iter().collect();
+        cmds.sort();
+
+        let cmds = cmds
+            .iter()
+            .map(|(c, n)| format!("\"{}\":{}", c.mnemonic(), n))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        writeln!(
+            w,
+            "{{\"steps\":{},\"inputs\":{},\"outputs\":{},\"cmds\":{{{}}},\"hits\":[{}],\"reads\":[{}],\"writes\":[{}]}}",
+            self.steps,
+            self.inputs,
+            self.outputs,
+            cmds,
+            by_key(&self.hits),
+            by_key(&self.reads),
+            by_key(&self.writes)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::computer::Computer;
+    use std::collections::VecDeque;
+
+    fn profile(mem: Vec<crate::Bit>) -> Profile {
+        let mut cin = VecDeque::from(vec![3]);
+        let mut cout = vec![];
+        let mut prof = Profile::new();
+
+        let mut c = Computer::new(mem, &mut cin, &mut cout);
+        c.set_tracer(Box::new(&mut prof));
+        c.run().unwrap();
+        drop(c);
+
+        prof
+    }
+
+    #[test]
+    fn counts() {
+        // Count the input down to zero, outputting as it goes
+        let prof = profile(vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, 0]);
+
+        assert_eq!(prof.steps, 11);
+        assert_eq!((prof.inputs, prof.outputs), (1, 3));
+        assert_eq!(prof.cmds[&Cmd::JumpTrue], 3);
+        assert_eq!(prof.hits[&2], 3);
+        assert_eq!(prof.reads[&12], 9);
+        assert_eq!(prof.writes[&12], 4);
+
+        let report = prof.report(1);
+        assert!(report.starts_with("steps=11 inputs=1 outputs=3\n"));
+        assert!(report.contains("\noutput               3  27.27%\n"));
+        assert!(report.contains("\naddr/hits        count\n2                    3\n"));
+
+        let mut json = vec![];
+        profile(vec![1101, 2, 3, 5, 99, 0])
+            .write_json(&mut json)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "{\"steps\":2,\"inputs\":0,\"outputs\":0,\"cmds\":{\"add\":1,\"halt\":1},\
+             \"hits\":[[0,1],[4,1]],\"reads\":[],\"writes\":[[5,1]]}\n"
+        );
+    }
+
+    #[test]
+    fn user_ops() {
+        let (mut cin, mut cout) = (VecDeque::new(), vec![]);
+        let mut prof = Profile::new();
+
+        let mut c = Computer::new(vec![42, 43, 42, 99], &mut cin, &mut cout);
+        c.add_op(42, &[], |_| Ok(()));
+        c.add_op(43, &[], |_| Ok(()));
+        c.set_tracer(Box::new(&mut prof));
+        c.run().unwrap();
+        drop(c);
+
+        let mut json = vec![];
+        prof.write_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.contains("\"cmds\":{\"halt\":1,\"user42\":2,\"user43\":1}"));
+        assert!(prof
+            .report(0)
+            .contains("\nuser42               2  50.00%\n"));
+    }
+}
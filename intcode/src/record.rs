@@ -0,0 +1,322 @@
+//! Records everything that goes in and out of a computer so the run can be replayed exactly,
+//! without whatever threads or person fed it the first time.
+//!
+//! Recordings are plain text with one value per line:
+//!
+//! ```text
+//! <tag> in|out <step> <idx> <value>
+//! ```
+//!
+//! `step` is how many instructions had run before the one doing the IO and `idx` is where that
+//! instruction starts.  The tag tells apart the machines sharing a recording so it can't have
+//! any whitespace in it.
+
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, LineWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::error::CompError::{InputErrStr, InvalidRecord, RecordErr, ReplayDiverged};
+use crate::error::Result;
+use crate::input::Input;
+use crate::output::Output;
+use crate::word::Word;
+use crate::Bit;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Dir {
+    In,
+    Out,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Record<W = Bit> {
+    pub tag: String,
+    pub dir: Dir,
+    pub step: usize,
+    pub addr: usize,
+    pub value: W,
+}
+
+impl<W: Display> Display for Record<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let dir = match self.dir {
+            Dir::In => "in",
+            Dir::Out => "out",
+        };
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.tag, dir, self.step, self.addr, self.value
+        )
+    }
+}
+
+/// Where recordings go.  Clones all write to the same place, so every machine in a run can share
+/// one recording even when they're on different threads.
+#[derive(Clone)]
+pub struct Log(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl Log {
+    pub fn new(w: impl Write + Send + 'static) -> Self {
+        Log(Arc::new(Mutex::new(Box::new(w))))
+    }
+
+    /// Every line is flushed as it's written so a crash doesn't lose what led up to it
+    pub fn create<P: AsRef<Path>>(p: P) -> std::io::Result<Self> {
+        Ok(Log::new(LineWriter::new(File::create(p)?)))
+    }
+
+    fn write<W: Display>(&self, rec: &Record<W>) -> Result<(), W> {
+        // A recorder that panicked mid line can't hurt anyone else's lines
+        let mut w = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(w, "{}", rec).map_err(RecordErr)
+    }
+}
+
+/// Panics on a tag that would make the recording unreadable
+fn checked_tag(tag: &str) -> String {
+    if tag.is_empty() || tag.contains(char::is_whitespace) {
+        panic!(
+            "Recording tags can't be empty or have whitespace in them: {:?}",
+            tag
+        );
+    }
+    tag.to_owned()
+}
+
+/// Where the computer is, as told by [`Input::at`] and [`Output::at`]
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone, Hash)]
+struct At {
+    step: usize,
+    addr: usize,
+}
+
+/// Hands out whatever `inner` does, writing each value down first
+pub struct RecordInput<I> {
+    inner: I,
+    log: Log,
+    tag: String,
+    at: At,
+}
+
+impl<I> RecordInput<I> {
+    /// Panics if the tag is empty or has any whitespace
+    pub fn new(inner: I, log: &Log, tag: &str) -> Self {
+        RecordInput {
+            inner,
+            log: log.clone(),
+            tag: checked_tag(tag),
+            at: At::default(),
+        }
+    }
+
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<W: Word, I: Input<W>> Input<W> for RecordInput<I> {
+    fn get_in(&mut self) -> Result<W, W> {
+        let value = self.inner.get_in()?;
+        self.log.write(&Record {
+            tag: self.tag.clone(),
+            dir: Dir::In,
+            step: self.at.step,
+            addr: self.at.addr,
+            value: value.clone(),
+        })?;
+        Ok(value)
+    }
+
+    fn at(&mut self, step: usize, addr: usize) {
+        self.at = At { step, addr };
+        self.inner.at(step, addr);
+    }
+}
+
+pub struct RecordOutput<O> {
+    inner: O,
+    log: Log,
+    tag: String,
+    at: At,
+}
+
+impl<O> RecordOutput<O> {
+    /// Panics if the tag is empty or has any whitespace
+    pub fn new(inner: O, log: &Log, tag: &str) -> Self {
+        RecordOutput {
+            inner,
+            log: log.clone(),
+            tag: checked_tag(tag),
+            at: At::default(),
+        }
+    }
+
+    pub fn into_inner(self) -> O {
+        self.inner
+    }
+}
+
+impl<W: Word, O: Output<W>> Output<W> for RecordOutput<O> {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
+        self.log.write(&Record {
+            tag: self.tag.clone(),
+            dir: Dir::Out,
+            step: self.at.step,
+            addr: self.at.addr,
+            value: n.clone(),
+        })?;
+        self.inner.put_out(n)
+    }
+
+    fn at(&mut self, step: usize, addr: usize) {
+        self.at = At { step, addr };
+        self.inner.at(step, addr);
+    }
+}
+
+fn parse_line<W: Word>(line: &str, lnum: usize) -> Result<Record<W>, W> {
+    let bad = |what: &str| InvalidRecord(lnum, format!("{} in {:?}", what, line));
+
+    let fields: Vec<_> = line.split_whitespace().collect();
+    let (tag, dir, step, addr, value) = match fields.as_slice() {
+        [tag, dir, step, addr, value] => (tag, dir, step, addr, value),
+        _ => return Err(bad("expected <tag> in|out <step> <idx> <value>")),
+    };
+
+    Ok(Record {
+        tag: tag.to_string(),
+        dir: match *dir {
+            "in" => Dir::In,
+            "out" => Dir::Out,
+            _ => return Err(bad("invalid direction")),
+        },
+        step: step.parse().map_err(|_| bad("invalid step"))?,
+        addr: addr.parse().map_err(|_| bad("invalid idx"))?,
+        value: value.parse().map_err(|_| bad("invalid value"))?,
+    })
+}
+
+pub fn read_from<W: Word>(r: &mut dyn BufRead) -> Result<Vec<Record<W>>, W> {
+    r.lines()
+        .enumerate()
+        .filter(|(_, l)| l.as_ref().map_or(true, |l| !l.trim().is_empty()))
+        .map(|(i, l)| parse_line(&l.map_err(RecordErr)?, i + 1))
+        .collect()
+}
+
+pub fn load<W: Word, P: AsRef<Path>>(p: P) -> Result<Vec<Record<W>>, W> {
+    let f = File::open(p).map_err(RecordErr)?;
+    read_from(&mut BufReader::new(f))
+}
+
+/// Feeds back the inputs one machine was recorded taking, failing as soon as it asks for one
+/// anywhere the recording didn't
+pub struct Replay<W = Bit> {
+    tag: String,
+    inputs: VecDeque<Record<W>>,
+    at: Option<At>,
+}
+
+impl<W: Word> Replay<W> {
+    pub fn new(records: &[Record<W>], tag: &str) -> Self {
+        Replay {
+            tag: tag.to_owned(),
+            inputs: records
+                .iter()
+                .filter(|r| r.dir == Dir::In && r.tag == tag)
+                .cloned()
+                .collect(),
+            at: None,
+        }
+    }
+
+    pub fn open<P: AsRef<Path>>(p: P, tag: &str) -> Result<Self, W> {
+        Ok(Replay::new(&load(p)?, tag))
+    }
+
+    /// How many recorded inputs haven't been asked for yet
+    pub fn remaining(&self) -> usize {
+        self.inputs.len()
+    }
+}
+
+impl<W: Word> Input<W> for Replay<W> {
+    fn get_in(&mut self) -> Result<W, W> {
+        let rec = self
+            .inputs
+            .pop_front()
+            .ok_or(InputErrStr("The recording ran out of inputs"))?;
+
+        // Anything that doesn't say where it is, like transpiled code, just gets the values
+        if let Some(at) = self.at.take() {
+            if (rec.step, rec.addr) != (at.step, at.addr) {
+                return Err(ReplayDiverged(
+                    self.tag.clone(),
+                    (rec.step, rec.addr),
+                    (at.step, at.addr),
+                ));
+            }
+        }
+
+        Ok(rec.value)
+    }
+
+    fn at(&mut self, step: usize, addr: usize) {
+        self.at = Some(At { step, addr });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::computer::Computer;
+
+    #[test]
+    fn round_trip() {
+        let path = std::env::temp_dir().join(format!("intcode-record-{}", std::process::id()));
+
+        // Outputs the sum of two inputs
+        let mem = vec![3, 11, 3, 12, 1, 11, 12, 13, 4, 13, 99, 0, 0, 0];
+        let log = Log::create(&path).unwrap();
+        let mut cin = RecordInput::new(VecDeque::from(vec![4, 5]), &log, "sum");
+        let mut cout = RecordOutput::new(vec![], &log, "sum");
+        Computer::new(mem.clone(), &mut cin, &mut cout)
+            .run()
+            .unwrap();
+        drop(log);
+        assert_eq!(cout.into_inner(), vec![9]);
+
+        let records: Vec<Record> = load(&path).unwrap();
+        let lines: Vec<_> = records.iter().map(|r| r.to_string()).collect();
+        assert_eq!(lines, vec!["sum in 0 0 4", "sum in 1 2 5", "sum out 3 8 9"]);
+
+        let mut replay = Replay::new(&records, "sum");
+        let mut cout = vec![];
+        Computer::new(mem, &mut replay, &mut cout).run().unwrap();
+        assert_eq!(cout, vec![9]);
+        assert_eq!(replay.remaining(), 0);
+
+        // Asking for the first input a step late isn't the same run
+        let mut replay = Replay::open(&path, "sum").unwrap();
+        let mut cout = vec![];
+        let mem = vec![1101, 0, 0, 0, 3, 0, 99];
+        match Computer::new(mem, &mut replay, &mut cout).run() {
+            Err(ReplayDiverged(tag, (0, 0), (1, 4))) => assert_eq!(tag, "sum"),
+            other => panic!("Expected the replay to diverge, got {:?}", other),
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Recording tags can't be empty or have whitespace in them: \"amp A\""
+    )]
+    fn spaced_tag() {
+        RecordOutput::new(Vec::<Bit>::new(), &Log::new(std::io::sink()), "amp A");
+    }
+}
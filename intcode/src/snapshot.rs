@@ -0,0 +1,154 @@
+//! A detached copy of a [`Computer`](crate::computer::Computer)'s state.
+//!
+//! Snapshots are saved as plain text, one `key=value` pair per line, in this order:
+//!
+//! ```text
+//! intcode-snapshot 1
+//! idx=<instruction pointer>
+//! rel=<relative base>
+//! pending=<comma separated values fed but not yet consumed>
+//! mem=<comma separated memory, the same format `Computer::get_bits` reads>
+//! ```
+//!
+//! Empty lists are written as nothing after the `=`.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::CompError::{InvalidSnapshot, SnapshotErr};
+use crate::error::Result;
+use crate::Bit;
+
+const HEADER: &str = "intcode-snapshot 1";
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Snapshot {
+    pub mem: Vec<Bit>,
+    pub idx: usize,
+    pub rel: Bit,
+    pub pending: Vec<Bit>,
+}
+
+fn write_list(w: &mut dyn Write, key: &str, bits: &[Bit]) -> std::io::Result<()> {
+    write!(w, "{}=", key)?;
+    for (i, b) in bits.iter().enumerate() {
+        if i > 0 {
+            w.write_all(b",")?;
+        }
+        write!(w, "{}", b)?;
+    }
+    writeln!(w)
+}
+
+fn field<'l>(line: Option<&'l str>, lnum: usize, key: &str) -> Result<&'l str> {
+    let line = line.ok_or_else(|| InvalidSnapshot(lnum, format!("missing the {} line", key)))?;
+
+    match line.split_once('=') {
+        Some((k, v)) if k == key => Ok(v),
+        _ => Err(InvalidSnapshot(
+            lnum,
+            format!("expected {}=..., got {}", key, line),
+        )),
+    }
+}
+
+fn parse<T: FromStr>(s: &str, lnum: usize) -> Result<T> {
+    s.trim()
+        .parse()
+        .map_err(|_| InvalidSnapshot(lnum, format!("invalid number {}", s)))
+}
+
+fn parse_list(s: &str, lnum: usize) -> Result<Vec<Bit>> {
+    if s.trim().is_empty() {
+        Ok(vec![])
+    } else {
+        s.split(',').map(|b| parse(b, lnum)).collect()
+    }
+}
+
+impl Snapshot {
+    pub fn write_to(&self, w: &mut dyn Write) -> Result<()> {
+        let mut go = || -> std::io::Result<()> {
+            writeln!(w, "{}", HEADER)?;
+            writeln!(w, "idx={}", self.idx)?;
+            writeln!(w, "rel={}", self.rel)?;
+            write_list(w, "pending", &self.pending)?;
+            write_list(w, "mem", &self.mem)?;
+            w.flush()
+        };
+
+        go().map_err(SnapshotErr)
+    }
+
+    pub fn read_from(r: &mut dyn BufRead) -> Result<Self> {
+        let lines = r.lines().collect::<std::io::Result<Vec<String>>>();
+        let lines = lines.map_err(SnapshotErr)?;
+        let mut lines = lines.iter().map(String::as_str);
+
+        match lines.next() {
+            Some(HEADER) => (),
+            other => {
+                return Err(InvalidSnapshot(
+                    1,
+                    format!("expected the header {:?}, got {:?}", HEADER, other),
+                ))
+            }
+        }
+
+        let idx = parse(field(lines.next(), 2, "idx")?, 2)?;
+        let rel = parse(field(lines.next(), 3, "rel")?, 3)?;
+        let pending = parse_list(field(lines.next(), 4, "pending")?, 4)?;
+        let mem = parse_list(field(lines.next(), 5, "mem")?, 5)?;
+
+        Ok(Snapshot {
+            mem,
+            idx,
+            rel,
+            pending,
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, p: P) -> Result<()> {
+        let f = File::create(p).map_err(SnapshotErr)?;
+        self.write_to(&mut BufWriter::new(f))
+    }
+
+    pub fn load<P: AsRef<Path>>(p: P) -> Result<Self> {
+        let f = File::open(p).map_err(SnapshotErr)?;
+        Snapshot::read_from(&mut BufReader::new(f))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let snap = Snapshot {
+            mem: vec![109, -1, 204, 3, 99],
+            idx: 2,
+            rel: -7,
+            pending: vec![],
+        };
+
+        let mut buf = vec![];
+        snap.write_to(&mut buf).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf.clone()).unwrap(),
+            "intcode-snapshot 1\nidx=2\nrel=-7\npending=\nmem=109,-1,204,3,99\n"
+        );
+
+        assert_eq!(Snapshot::read_from(&mut buf.as_slice()).unwrap(), snap);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidSnapshot(3")]
+    fn bad_field() {
+        let mut s = "intcode-snapshot 1\nidx=2\nrelative=-7\npending=\nmem=99\n".as_bytes();
+        Snapshot::read_from(&mut s).unwrap();
+    }
+}
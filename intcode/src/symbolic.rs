@@ -0,0 +1,559 @@
+//! Runs a program where some memory cells and inputs are unknowns.
+//!
+//! Every value is an [`Expr`] that folds down to a constant whenever its operands are known, so
+//! the concrete parts of a program run exactly like they would on a
+//! [`Computer`](crate::computer::Computer). Comparisons of unknowns stay symbolic; once a jump,
+//! opcode, destination or relative base depends on one, the value the current guesses give is
+//! used and a [`Constraint`] is recorded so answers that would take a different path can be
+//! thrown out by [`Symbolic::solve`].
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+use std::time::Instant;
+
+use crate::computer::{Cmd, Instruction, Limits, Mode};
+use crate::error::{CompError::*, Result};
+use crate::word::Word;
+use crate::Bit;
+
+pub type Sym = Rc<Expr>;
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub enum Expr {
+    Const(Bit),
+    Var(String),
+    Add(Sym, Sym),
+    Mul(Sym, Sym),
+    Lt(Sym, Sym),
+    Eq(Sym, Sym),
+    /// A read through an unknown address, from memory as it was at the time
+    Load(Sym, Rc<Vec<Sym>>),
+}
+
+use Expr::*;
+
+fn konst(v: Bit) -> Sym {
+    Rc::new(Const(v))
+}
+
+impl Expr {
+    pub fn var(name: &str) -> Sym {
+        Rc::new(Var(name.to_owned()))
+    }
+
+    pub fn konst(&self) -> Option<Bit> {
+        match self {
+            Const(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Evaluates with the unknowns set from `model`; `None` if one is missing or it overflows
+    pub fn eval(&self, model: &HashMap<String, Bit>) -> Option<Bit> {
+        match self {
+            Const(v) => Some(*v),
+            Var(name) => model.get(name).copied(),
+            Add(a, b) => a.eval(model)?.checked_add(b.eval(model)?),
+            Mul(a, b) => a.eval(model)?.checked_mul(b.eval(model)?),
+            Lt(a, b) => Some((a.eval(model)? < b.eval(model)?) as Bit),
+            Eq(a, b) => Some((a.eval(model)? == b.eval(model)?) as Bit),
+            Load(addr, mem) => {
+                let addr = addr.eval(model)?.to_usize()?;
+                mem.get(addr).map_or(Some(0), |v| v.eval(model))
+            }
+        }
+    }
+
+    /// Every unknown the expression depends on
+    pub fn vars(&self) -> BTreeSet<&str> {
+        let mut vars = BTreeSet::new();
+        self.collect_vars(&mut vars);
+        vars
+    }
+
+    fn collect_vars<'a>(&'a self, vars: &mut BTreeSet<&'a str>) {
+        match self {
+            Const(_) => (),
+            Var(name) => {
+                vars.insert(name);
+            }
+            Add(a, b) | Mul(a, b) | Lt(a, b) | Eq(a, b) => {
+                a.collect_vars(vars);
+                b.collect_vars(vars);
+            }
+            Load(addr, mem) => {
+                addr.collect_vars(vars);
+                for v in mem.iter() {
+                    v.collect_vars(vars);
+                }
+            }
+        }
+    }
+
+    /// The expression as `c + a*x + b*y ...`, if it is linear in its unknowns
+    pub fn linear(&self) -> Option<(Bit, BTreeMap<&str, Bit>)> {
+        match self {
+            Const(v) => Some((*v, BTreeMap::new())),
+            Var(name) => Some((0, vec![(name.as_str(), 1)].into_iter().collect())),
+            Add(a, b) => {
+                let (ca, mut xa) = a.linear()?;
+                let (cb, xb) = b.linear()?;
+                for (x, n) in xb {
+                    let sum = xa.get(x).copied().unwrap_or(0).checked_add(n)?;
+                    xa.insert(x, sum);
+                }
+                Some((ca.checked_add(cb)?, xa))
+            }
+            Mul(a, b) => {
+                let ((c, xs), (k, ks)) = (a.linear()?, b.linear()?);
+                let ((c, xs), k) = match (xs.is_empty(), ks.is_empty()) {
+                    (_, true) => ((c, xs), k),
+                    (true, false) => ((k, ks), c),
+                    (false, false) => return None,
+                };
+
+                let xs = xs
+                    .into_iter()
+                    .map(|(x, n)| n.checked_mul(k).map(|n| (x, n)))
+                    .collect::<Option<_>>()?;
+                Some((c.checked_mul(k)?, xs))
+            }
+            Lt(..) | Eq(..) | Load(..) => None,
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Const(v) => write!(f, "{}", v),
+            Var(name) => f.write_str(name),
+            Add(a, b) => write!(f, "({} + {})", a, b),
+            Mul(a, b) => write!(f, "({} * {})", a, b),
+            Lt(a, b) => write!(f, "({} < {})", a, b),
+            Eq(a, b) => write!(f, "({} == {})", a, b),
+            Load(addr, _) => write!(f, "[{}]", addr),
+        }
+    }
+}
+
+/// A decision the run made about an unknown: `cond` came out non-zero iff `holds`
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Constraint {
+    /// Where the instruction that made the decision starts
+    pub at: usize,
+    pub cond: Sym,
+    pub holds: bool,
+}
+
+impl Constraint {
+    pub fn check(&self, model: &HashMap<String, Bit>) -> bool {
+        self.cond.eval(model).map(|v| v != 0) == Some(self.holds)
+    }
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let op = if self.holds { "!=" } else { "==" };
+        write!(f, "{}: {} {} 0", self.at, self.cond, op)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbolic {
+    mem: Rc<Vec<Sym>>,
+    idx: usize,
+    rel: Bit,
+    pending: VecDeque<Sym>,
+    /// How many fresh unknowns the inputs have made
+    fresh: usize,
+    outputs: Vec<Sym>,
+    constraints: Vec<Constraint>,
+    /// The guesses for every unknown, used to pick a path
+    model: HashMap<String, Bit>,
+    steps: usize,
+    limits: Limits,
+}
+
+impl Symbolic {
+    pub fn new(mem: &[Bit]) -> Self {
+        Symbolic {
+            mem: Rc::new(mem.iter().map(|v| konst(*v)).collect()),
+            idx: 0,
+            rel: 0,
+            pending: VecDeque::new(),
+            fresh: 0,
+            outputs: vec![],
+            constraints: vec![],
+            model: HashMap::new(),
+            steps: 0,
+            limits: Limits::default(),
+        }
+    }
+
+    /// Makes the cell at `addr` an unknown; whatever was there is the initial guess
+    pub fn unknown(&mut self, addr: usize, name: &str) {
+        let guess = self.read(addr).konst().unwrap_or(0);
+        self.model.insert(name.to_owned(), guess);
+        self.write(addr, Expr::var(name));
+    }
+
+    /// Sets the guess for an unknown, which picks the path taken at branches that depend on it
+    pub fn guess(&mut self, name: &str, v: Bit) {
+        self.model.insert(name.to_owned(), v);
+    }
+
+    /// Queues a value for the next input; inputs past the queue become unknowns named `in0`,
+    /// `in1`, ... guessed as 0
+    pub fn feed(&mut self, v: Sym) {
+        self.pending.push_back(v);
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn mem(&self) -> &[Sym] {
+        &self.mem
+    }
+
+    pub fn outputs(&self) -> &[Sym] {
+        &self.outputs
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    pub fn model(&self) -> &HashMap<String, Bit> {
+        &self.model
+    }
+
+    fn read(&self, addr: usize) -> Sym {
+        self.mem.get(addr).cloned().unwrap_or_else(|| konst(0))
+    }
+
+    fn write(&mut self, addr: usize, v: Sym) {
+        let mem = Rc::make_mut(&mut self.mem);
+        if addr >= mem.len() {
+            mem.resize(addr + 1, konst(0));
+        }
+        mem[addr] = v;
+    }
+
+    /// The value the guesses give `v`, pinning it there with a constraint when it isn't constant
+    fn concretize(&mut self, at: usize, v: &Sym) -> Result<Bit> {
+        if let Some(c) = v.konst() {
+            return Ok(c);
+        }
+
+        let c = v
+            .eval(&self.model)
+            .ok_or_else(|| Unresolved(at, v.to_string()))?;
+        self.constraints.push(Constraint {
+            at,
+            cond: Rc::new(Eq(v.clone(), konst(c))),
+            holds: true,
+        });
+        Ok(c)
+    }
+
+    fn addr(at: usize, v: Bit, mode: Mode, cmd: Cmd) -> Result<usize> {
+        v.to_usize().ok_or(InvalidAddress(at, Some(v), mode, cmd))
+    }
+
+    fn get(&mut self, at: usize, ins: Instruction, i: usize) -> Result<Sym> {
+        let mode = ins.modes()?[i];
+        let param = self.read(at + 1 + i);
+
+        let addr = match mode {
+            Mode::Immediate => return Ok(param),
+            Mode::Position => param,
+            Mode::Relative => add(at, ins.cmd(), &konst(self.rel), &param)?,
+        };
+
+        match addr.konst() {
+            Some(a) => Ok(self.read(Symbolic::addr(at, a, mode, ins.cmd())?)),
+            None => Ok(Rc::new(Load(addr, self.mem.clone()))),
+        }
+    }
+
+    fn put(&mut self, at: usize, ins: Instruction, i: usize, v: Sym) -> Result<()> {
+        let mode = ins.modes()?[i];
+        let param = self.read(at + 1 + i);
+
+        let addr = match mode {
+            Mode::Immediate => return Err(InvalidOutputMode(at, ins.cmd())),
+            Mode::Position => param,
+            Mode::Relative => add(at, ins.cmd(), &konst(self.rel), &param)?,
+        };
+        let addr = self.concretize(at, &addr)?;
+        let addr = Symbolic::addr(at, addr, mode, ins.cmd())?;
+
+        if let Some(max) = self.limits.max_addr {
+            if addr > max {
+                return Err(MemLimit(at, addr, max));
+            }
+        }
+
+        self.write(addr, v);
+        Ok(())
+    }
+
+    /// Runs one instruction, returning true once the program halts
+    pub fn step(&mut self) -> Result<bool> {
+        use Cmd::*;
+
+        let at = self.idx;
+        if let Some(max) = self.limits.max_steps {
+            if self.steps >= max {
+                return Err(StepLimit(at, max));
+            }
+        }
+        if self.limits.deadline.is_some_and(|d| Instant::now() >= d) {
+            return Err(Timeout(at));
+        }
+
+        let op = self.read(at);
+        let ins = Instruction::decode(&self.concretize(at, &op)?)?;
+        let cmd = ins.cmd();
+        let mut next = at + 1 + cmd.arity();
+
+        match cmd {
+            Add | Multiply | LessThan | Equals => {
+                let (a, b) = (self.get(at, ins, 0)?, self.get(at, ins, 1)?);
+                let v = match cmd {
+                    Add => add(at, cmd, &a, &b)?,
+                    Multiply => mul(at, cmd, &a, &b)?,
+                    LessThan => cmp(&a, &b, Lt, |a, b| a < b),
+                    _ => cmp(&a, &b, Eq, |a, b| a == b),
+                };
+                self.put(at, ins, 2, v)?;
+            }
+
+            Input => {
+                let v = self.pending.pop_front().unwrap_or_else(|| {
+                    let name = format!("in{}", self.fresh);
+                    self.fresh += 1;
+                    self.model.insert(name.clone(), 0);
+                    Expr::var(&name)
+                });
+                self.put(at, ins, 0, v)?;
+            }
+
+            Output => {
+                let v = self.get(at, ins, 0)?;
+                self.outputs.push(v);
+            }
+
+            JumpTrue | JumpFalse => {
+                let cond = self.get(at, ins, 0)?;
+                let taken = match cond.konst() {
+                    Some(c) => c != 0,
+                    None => {
+                        let c = cond
+                            .eval(&self.model)
+                            .ok_or_else(|| Unresolved(at, cond.to_string()))?;
+                        self.constraints.push(Constraint {
+                            at,
+                            cond,
+                            holds: c != 0,
+                        });
+                        c != 0
+                    }
+                } == (cmd == JumpTrue);
+
+                if taken {
+                    let to = self.get(at, ins, 1)?;
+                    let to = self.concretize(at, &to)?;
+                    next = Symbolic::addr(at, to, Mode::Immediate, cmd)?;
+                }
+            }
+
+            AdjustRel => {
+                let by = self.get(at, ins, 0)?;
+                let by = self.concretize(at, &by)?;
+                self.rel = self
+                    .rel
+                    .checked_add(by)
+                    .ok_or(ArithOverflow(at, cmd, self.rel, by))?;
+            }
+
+            Halt => {
+                self.steps += 1;
+                return Ok(true);
+            }
+
+            User { .. } => return Err(InvalidInstruction(Bit::from(cmd.opcode()))),
+        }
+
+        self.idx = next;
+        self.steps += 1;
+        Ok(false)
+    }
+
+    /// Runs until the program halts, returning how many instructions ran
+    pub fn run(&mut self) -> Result<usize> {
+        let mut steps = 1;
+        while !self.step()? {
+            steps += 1;
+        }
+        Ok(steps)
+    }
+
+    /// Finds values for the unknowns in `ranges` that make `e` equal `target` while keeping every
+    /// constraint of this run; any other unknowns keep their guesses.
+    ///
+    /// Linear expressions only search every unknown but the last, the rest try everything.
+    pub fn solve(
+        &self,
+        e: &Expr,
+        target: Bit,
+        ranges: &[(&str, RangeInclusive<Bit>)],
+    ) -> Option<HashMap<String, Bit>> {
+        let mut model = self.model.clone();
+        let ok = |model: &HashMap<String, Bit>| {
+            e.eval(model) == Some(target) && self.constraints.iter().all(|c| c.check(model))
+        };
+
+        let (last, rest) = ranges.split_last()?;
+        let found = match e.linear() {
+            Some((_, xs)) if xs.get(last.0).copied().unwrap_or(0) != 0 => {
+                let k = xs[last.0];
+                search(&mut model, rest, &mut |model| {
+                    model.insert(last.0.to_owned(), 0);
+                    let left = target.checked_sub(e.eval(model)?)?;
+                    if left % k != 0 || !last.1.contains(&(left / k)) {
+                        return None;
+                    }
+
+                    model.insert(last.0.to_owned(), left / k);
+                    Some(()).filter(|_| ok(model))
+                })
+            }
+            _ => search(&mut model, ranges, &mut |model| {
+                Some(()).filter(|_| ok(model))
+            }),
+        };
+
+        found.map(|_| {
+            ranges
+                .iter()
+                .map(|(x, _)| (x.to_string(), model[*x]))
+                .collect()
+        })
+    }
+}
+
+/// Tries every combination of `ranges` until `f` accepts one, leaving it in `model`
+fn search(
+    model: &mut HashMap<String, Bit>,
+    ranges: &[(&str, RangeInclusive<Bit>)],
+    f: &mut dyn FnMut(&mut HashMap<String, Bit>) -> Option<()>,
+) -> Option<()> {
+    match ranges.split_first() {
+        None => f(model),
+        Some(((x, r), rest)) => r.clone().find_map(|v| {
+            model.insert(x.to_string(), v);
+            search(model, rest, f)
+        }),
+    }
+}
+
+fn add(at: usize, cmd: Cmd, a: &Sym, b: &Sym) -> Result<Sym> {
+    Ok(match (a.konst(), b.konst()) {
+        (Some(x), Some(y)) => konst(x.checked_add(y).ok_or(ArithOverflow(at, cmd, x, y))?),
+        (Some(0), _) => b.clone(),
+        (_, Some(0)) => a.clone(),
+        _ => Rc::new(Add(a.clone(), b.clone())),
+    })
+}
+
+fn mul(at: usize, cmd: Cmd, a: &Sym, b: &Sym) -> Result<Sym> {
+    Ok(match (a.konst(), b.konst()) {
+        (Some(x), Some(y)) => konst(x.checked_mul(y).ok_or(ArithOverflow(at, cmd, x, y))?),
+        (Some(0), _) | (_, Some(0)) => konst(0),
+        (Some(1), _) => b.clone(),
+        (_, Some(1)) => a.clone(),
+        _ => Rc::new(Mul(a.clone(), b.clone())),
+    })
+}
+
+fn cmp(a: &Sym, b: &Sym, node: fn(Sym, Sym) -> Expr, f: fn(Bit, Bit) -> bool) -> Sym {
+    match (a.konst(), b.konst()) {
+        (Some(x), Some(y)) => konst(f(x, y) as Bit),
+        _ => Rc::new(node(a.clone(), b.clone())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solution(pairs: &[(&str, Bit)]) -> Option<HashMap<String, Bit>> {
+        Some(pairs.iter().map(|(x, v)| (x.to_string(), *v)).collect())
+    }
+
+    #[test]
+    fn linear() {
+        // [0] = [9] * [10] + [11]
+        let mut s = Symbolic::new(&[2, 9, 10, 0, 1, 0, 11, 0, 99, 0, 3, 0]);
+        s.unknown(9, "x");
+        s.unknown(11, "y");
+        assert_eq!(s.run().unwrap(), 3);
+
+        let e = s.mem()[0].clone();
+        assert_eq!(e.to_string(), "((x * 3) + y)");
+        assert_eq!(
+            e.linear().unwrap().1.into_iter().collect::<Vec<_>>(),
+            vec![("x", 3), ("y", 1)]
+        );
+        assert!(s.constraints().is_empty());
+
+        let ranges = [("x", 0..=9), ("y", 0..=2)];
+        assert_eq!(s.solve(&e, 20, &ranges), solution(&[("x", 6), ("y", 2)]));
+        assert_eq!(s.solve(&e, 30, &ranges), None);
+    }
+
+    #[test]
+    fn branches() {
+        // Outputs 1 if the input is 5, 0 otherwise
+        let mem = [3, 20, 1008, 20, 5, 21, 1005, 21, 12, 104, 0, 99, 104, 1, 99];
+        let mut s = Symbolic::new(&mem);
+        s.run().unwrap();
+
+        assert_eq!(s.outputs(), &[konst(0)]);
+        assert_eq!(s.constraints().len(), 1);
+        assert_eq!(s.constraints()[0].to_string(), "6: (in0 == 5) == 0");
+
+        // Asking for 0 works but anything on the other path is ruled out by the constraint
+        let out = s.outputs()[0].clone();
+        assert_eq!(s.solve(&out, 0, &[("in0", 0..=9)]), solution(&[("in0", 0)]));
+        assert_eq!(s.solve(&Expr::var("in0"), 5, &[("in0", 0..=9)]), None);
+
+        s = Symbolic::new(&mem);
+        s.feed(konst(5));
+        s.run().unwrap();
+        assert_eq!(s.outputs(), &[konst(1)]);
+        assert!(s.constraints().is_empty());
+    }
+
+    #[test]
+    fn unknown_addresses() {
+        // [0] = [[1]] + 1, a load through an unknown address
+        let mut s = Symbolic::new(&[1001, 5, 1, 0, 99, 7, 0, 40]);
+        s.unknown(1, "p");
+        s.run().unwrap();
+
+        let e = s.mem()[0].clone();
+        assert_eq!(e.to_string(), "([p] + 1)");
+        assert_eq!(e.linear(), None);
+        assert_eq!(s.solve(&e, 41, &[("p", 0..=9)]), solution(&[("p", 7)]));
+    }
+}
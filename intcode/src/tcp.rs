@@ -0,0 +1,107 @@
+//! [`Input`] and [`Output`] over a TCP connection, so machines in different processes can talk
+//! the way threads do over channels.
+//!
+//! Values are sent as decimal text, one per line, which makes it easy to poke at a machine with
+//! `nc` too.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use crate::error::CompError::{InputErr, InputErrStr, OutputErr};
+use crate::error::Result;
+use crate::input::Input;
+use crate::output::Output;
+use crate::word::Word;
+
+pub struct TcpInput {
+    reader: BufReader<TcpStream>,
+    line: String,
+}
+
+impl TcpInput {
+    pub fn new(stream: TcpStream) -> Self {
+        TcpInput {
+            reader: BufReader::new(stream),
+            line: String::new(),
+        }
+    }
+}
+
+impl<W: Word> Input<W> for TcpInput {
+    fn get_in(&mut self) -> Result<W, W> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return Err(InputErrStr("The connection was closed")),
+                Ok(_) if self.line.trim().is_empty() => continue,
+                Ok(_) => {
+                    return self
+                        .line
+                        .trim()
+                        .parse()
+                        .map_err(|_| InputErrStr("Got something that isn't a number"))
+                }
+                Err(e) => return Err(InputErr(Box::new(e))),
+            }
+        }
+    }
+}
+
+pub struct TcpOutput(pub TcpStream);
+
+impl<W: Word> Output<W> for TcpOutput {
+    fn put_out(&mut self, n: W) -> Result<(), W> {
+        // One write per value so it goes out in one piece as soon as it's made
+        self.0
+            .write_all(format!("{}\n", n).as_bytes())
+            .map_err(|e| OutputErr(Box::new(e)))
+    }
+}
+
+/// Both ends of one connection
+pub fn split(stream: TcpStream) -> io::Result<(TcpInput, TcpOutput)> {
+    stream.set_nodelay(true)?;
+    Ok((TcpInput::new(stream.try_clone()?), TcpOutput(stream)))
+}
+
+pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<(TcpInput, TcpOutput)> {
+    split(TcpStream::connect(addr)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::computer::Computer;
+    use crate::Bit;
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn remote_machine() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Adds up pairs of inputs until it's handed a zero
+        let machine = thread::spawn(move || {
+            let mem = vec![
+                3, 20, 1006, 20, 17, 3, 21, 1, 20, 21, 22, 4, 22, 1105, 1, 0, 99, 99, 0, 0, 0, 0, 0,
+            ];
+            let (mut cin, mut cout) = split(listener.accept().unwrap().0).unwrap();
+            let steps = Computer::new(mem, &mut cin, &mut cout).run().unwrap();
+            steps
+        });
+
+        let (mut cin, mut cout) = connect(addr).unwrap();
+        let mut sums = vec![];
+        for (a, b) in &[(1, 2), (-5, 40)] {
+            cout.put_out(*a).unwrap();
+            cout.put_out(*b).unwrap();
+            sums.push(Input::<Bit>::get_in(&mut cin).unwrap());
+        }
+        cout.put_out(0).unwrap();
+
+        assert_eq!(sums, vec![3, 35]);
+        assert!(machine.join().unwrap() > 0);
+        assert!(Input::<Bit>::get_in(&mut cin).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use std::io::Write;
+
+use crate::computer::{Cmd, Mode};
+use crate::error::CompError::TraceErr;
+use crate::error::Result;
+use crate::word::Word;
+use crate::Bit;
+
+/// Everything that happened while running a single instruction
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Event<W = Bit> {
+    pub addr: usize,
+    pub cmd: Cmd,
+    pub modes: Vec<Mode>,
+    /// The resolved value of every operand that was read, in order.  A jump that isn't taken
+    /// never reads its target so it only has the condition.
+    pub args: Vec<W>,
+    /// Every memory address an operand was loaded from, in order
+    pub reads: Vec<usize>,
+    /// The address and value written, if any
+    pub write: Option<(usize, W)>,
+    /// The relative base the operands were resolved against
+    pub rel: W,
+}
+
+pub trait Tracer<W = Bit> {
+    fn trace(&mut self, ev: &Event<W>) -> Result<(), W>;
+}
+
+impl<W, T: Tracer<W> + ?Sized> Tracer<W> for &mut T {
+    fn trace(&mut self, ev: &Event<W>) -> Result<(), W> {
+        (**self).trace(ev)
+    }
+}
+
+impl<W: Word> Tracer<W> for Vec<Event<W>> {
+    fn trace(&mut self, ev: &Event<W>) -> Result<(), W> {
+        self.push(ev.clone());
+        Ok(())
+    }
+}
+
+fn join<W: Word>(bits: &[W]) -> String {
+    bits.iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Writes every event as a JSON object on its own line
+pub struct JsonTracer<T: Write>(pub T);
+
+impl<W: Word, T: Write> Tracer<W> for JsonTracer<T> {
+    fn trace(&mut self, ev: &Event<W>) -> Result<(), W> {
+        let modes = ev
+            .modes
+            .iter()
+            .map(|m| format!("\"{}\"", m.to_string().to_lowercase()))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let write = match &ev.write {
+            Some((addr, val)) => format!("{{\"addr\":{},\"value\":{}}}", addr, val),
+            None => "null".to_owned(),
+        };
+
+        writeln!(
+            self.0,
+            "{{\"addr\":{},\"cmd\":\"{}\",\"modes\":[{}],\"args\":[{}],\"write\":{},\"rel\":{}}}",
+            ev.addr,
+            ev.cmd.mnemonic(),
+            modes,
+            join(&ev.args),
+            write,
+            ev.rel
+        )
+        .map_err(TraceErr)
+    }
+}
+
+/// Writes every event as a line of aligned columns, meant for reading or diffing
+pub struct TracePrinter<T: Write>(pub T);
+
+impl<W: Word, T: Write> Tracer<W> for TracePrinter<T> {
+    fn trace(&mut self, ev: &Event<W>) -> Result<(), W> {
+        let modes = ev
+            .modes
+            .iter()
+            .map(|m| m.to_string()[..3].to_owned())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let write = match &ev.write {
+            Some((addr, val)) => format!("[{}] <- {}", addr, val),
+            None => String::new(),
+        };
+
+        writeln!(
+            self.0,
+            "{:>6}  {:<9}  {:<11}  {:<30}  {:<24}  rel={}",
+            ev.addr,
+            ev.cmd.mnemonic(),
+            modes,
+            join(&ev.args),
+            write,
+            ev.rel
+        )
+        .map_err(TraceErr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::computer::Computer;
+    use std::collections::VecDeque;
+
+    fn trace(mem: Vec<Bit>, tracer: &mut dyn Tracer) {
+        let mut cin = VecDeque::from(vec![7]);
+        let mut cout = vec![];
+
+        let mut c = Computer::new(mem, &mut cin, &mut cout);
+        c.set_tracer(Box::new(tracer));
+        c.run().unwrap();
+    }
+
+    #[test]
+    fn events() {
+        let mut events = vec![];
+        trace(
+            vec![109, 10, 203, 0, 1005, 10, 9, 99, 0, 99, 0],
+            &mut events,
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                Event {
+                    addr: 0,
+                    cmd: Cmd::AdjustRel,
+                    modes: vec![Mode::Immediate],
+                    args: vec![10],
+                    reads: vec![],
+                    write: None,
+                    rel: 0,
+                },
+                Event {
+                    addr: 2,
+                    cmd: Cmd::Input,
+                    modes: vec![Mode::Relative],
+                    args: vec![],
+                    reads: vec![],
+                    write: Some((10, 7)),
+                    rel: 10,
+                },
+                Event {
+                    addr: 4,
+                    cmd: Cmd::JumpTrue,
+                    modes: vec![Mode::Position, Mode::Immediate],
+                    args: vec![7, 9],
+                    reads: vec![10],
+                    write: None,
+                    rel: 10,
+                },
+                Event {
+                    addr: 9,
+                    cmd: Cmd::Halt,
+                    modes: vec![],
+                    args: vec![],
+                    reads: vec![],
+                    write: None,
+                    rel: 10,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn writers() {
+        let mem = vec![1101, 2, 3, 5, 99, 0];
+
+        let mut json = JsonTracer(vec![]);
+        trace(mem.clone(), &mut json);
+        assert_eq!(
+            String::from_utf8(json.0).unwrap(),
+            "{\"addr\":0,\"cmd\":\"add\",\"modes\":[\"immediate\",\"immediate\",\"position\"],\
+             \"args\":[2,3],\"write\":{\"addr\":5,\"value\":5},\"rel\":0}\n\
+             {\"addr\":4,\"cmd\":\"halt\",\"modes\":[],\"args\":[],\"write\":null,\"rel\":0}\n"
+        );
+
+        let mut human = TracePrinter(vec![]);
+        trace(mem, &mut human);
+        let human = String::from_utf8(human.0).unwrap();
+        let lines: Vec<_> = human.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines[0],
+            "     0  add        Imm Imm Pos  2,3                             [5] <- 5                  rel=0"
+        );
+        assert_eq!(lines.len(), 2);
+    }
+}
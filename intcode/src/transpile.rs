@@ -0,0 +1,426 @@
+//! Turns a program into Rust source with one function per basic block of [`flow::graph`].
+//!
+//! The generated module looks like
+//!
+//! ```text
+//! pub const MEM: &[Bit] = &[...];
+//! pub fn run(input: &mut dyn Input, output: &mut dyn Output) -> Result<Vec<Bit>>;
+//! ```
+//!
+//! and `run` returns the final memory, the same as [`Computer::run`] would leave it.  Anything
+//! the generated code can't handle on its own hands the rest of the run over to a [`Computer`]:
+//! computed jumps to something other than the start of a block, writes into the compiled code,
+//! and anything that would be an error so the interpreter can report it.
+//!
+//! [`flow::graph`]: crate::flow::graph
+//! [`Computer`]: crate::computer::Computer
+//! [`Computer::run`]: crate::computer::Computer::run
+
+use std::fmt::Write;
+
+use crate::computer::{Cmd, Instruction, Mode};
+use crate::disasm::Operand;
+use crate::flow::{graph, Block};
+use crate::Bit;
+
+/// What the generated code leans on
+pub mod rt {
+    use std::convert::TryFrom;
+
+    use crate::computer::Computer;
+    use crate::error::Result;
+    use crate::input::Input;
+    use crate::memory::Memory;
+    use crate::output::Output;
+    use crate::snapshot::Snapshot;
+    use crate::Bit;
+
+    /// Where a block goes once it's done
+    #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+    pub enum Exit {
+        Block(usize),
+        /// Let the interpreter take it from this address
+        Interp(usize),
+        Halt,
+    }
+
+    pub struct State {
+        pub mem: Vec<Bit>,
+        pub rel: Bit,
+        code: Vec<bool>,
+    }
+
+    impl State {
+        /// `code` is every `(start, end)` range of memory that was compiled
+        pub fn new(mem: &[Bit], code: &[(usize, usize)]) -> Self {
+            let mut bits = vec![false; mem.len()];
+            for &(start, end) in code {
+                for b in &mut bits[start..end] {
+                    *b = true;
+                }
+            }
+
+            State {
+                mem: mem.to_vec(),
+                rel: 0,
+                code: bits,
+            }
+        }
+
+        #[inline]
+        pub fn read(&self, addr: usize) -> Bit {
+            self.mem.read(addr)
+        }
+
+        #[inline]
+        pub fn rel_addr(&self, off: Bit) -> Option<usize> {
+            self.rel.checked_add(off).and_then(State::addr)
+        }
+
+        #[inline]
+        pub fn addr(b: Bit) -> Option<usize> {
+            usize::try_from(b).ok()
+        }
+
+        /// Returns true if the write changed compiled code, which then can't be trusted anymore
+        #[inline]
+        pub fn write(&mut self, addr: usize, val: Bit) -> Result<bool> {
+            self.mem.write(addr, val)?;
+            Ok(self.code.get(addr).copied().unwrap_or(false))
+        }
+
+        pub fn interpret(
+            self,
+            idx: usize,
+            input: &mut dyn Input,
+            output: &mut dyn Output,
+        ) -> Result<Vec<Bit>> {
+            let snap = Snapshot {
+                mem: self.mem,
+                idx,
+                rel: self.rel,
+                pending: vec![],
+            };
+
+            let mut comp = Computer::restore(snap, input, output);
+            comp.run()?;
+            Ok(comp.mem)
+        }
+    }
+}
+
+/// How many values go on each line of `MEM`
+const MEM_WIDTH: usize = 16;
+
+/// The expression for an operand's value, or `None` if it's always an error
+fn value(op: Operand, at: usize) -> Option<String> {
+    match op.0 {
+        Mode::Immediate => Some(op.1.to_string()),
+        Mode::Position if op.1 >= 0 => Some(format!("s.read({})", op.1)),
+        Mode::Position => None,
+        Mode::Relative => Some(format!("s.read({})", rel_addr(op.1, at))),
+    }
+}
+
+/// The expression for the address an operand writes to, or `None` if it's always an error
+fn dest(op: Operand, at: usize) -> Option<String> {
+    match op.0 {
+        Mode::Position if op.1 >= 0 => Some(op.1.to_string()),
+        Mode::Relative => Some(rel_addr(op.1, at)),
+        _ => None,
+    }
+}
+
+fn rel_addr(off: Bit, at: usize) -> String {
+    format!(
+        "match s.rel_addr({}) {{ Some(a) => a, None => return Ok(Exit::Interp({})) }}",
+        off, at
+    )
+}
+
+/// The statements for a single instruction; returns false if the block can't go on past it
+fn ins(out: &mut String, at: usize, ins: Instruction, ops: &[Operand]) -> bool {
+    let next = at + 1 + ops.len();
+    let bail = format!("return Ok(Exit::Interp({}))", at);
+
+    let vals: Option<Vec<String>> = ops.iter().map(|o| value(*o, at)).collect();
+    let (vals, d) = match (vals, ins.cmd()) {
+        (_, Cmd::Input) => (Some(vec![]), dest(ops[0], at)),
+        (Some(v), Cmd::Add)
+        | (Some(v), Cmd::Multiply)
+        | (Some(v), Cmd::LessThan)
+        | (Some(v), Cmd::Equals) => (Some(v), dest(ops[2], at)),
+        (v, _) => (v, Some(String::new())),
+    };
+    let (vals, d) = match (vals, d) {
+        (Some(v), Some(d)) => (v, d),
+        _ => {
+            writeln!(out, "    {};", bail).unwrap();
+            return false;
+        }
+    };
+
+    let write = |out: &mut String, val: &str| {
+        writeln!(out, "    let d = {};", d).unwrap();
+        writeln!(out, "    if s.write(d, {})? {{", val).unwrap();
+        writeln!(out, "        return Ok(Exit::Interp({}));", next).unwrap();
+        writeln!(out, "    }}").unwrap();
+    };
+
+    match ins.cmd() {
+        Cmd::Add | Cmd::Multiply => {
+            let f = if ins.cmd() == Cmd::Add {
+                "checked_add"
+            } else {
+                "checked_mul"
+            };
+            writeln!(
+                out,
+                "    let v = match Bit::{}({}, {}) {{ Some(v) => v, None => {} }};",
+                f, vals[0], vals[1], bail
+            )
+            .unwrap();
+            write(out, "v");
+        }
+
+        Cmd::LessThan | Cmd::Equals => {
+            let op = if ins.cmd() == Cmd::LessThan {
+                "<"
+            } else {
+                "=="
+            };
+            // Fold the comparison when it's all immediates, clippy hates `1 == 1`
+            match (ops[0].0, ops[1].0) {
+                (Mode::Immediate, Mode::Immediate) => {
+                    let (a, b) = (ops[0].1, ops[1].1);
+                    let v = if op == "<" { a < b } else { a == b };
+                    write(out, &(v as Bit).to_string());
+                }
+                _ => {
+                    writeln!(out, "    let v = ({} {} {}) as Bit;", vals[0], op, vals[1]).unwrap();
+                    write(out, "v");
+                }
+            }
+        }
+
+        Cmd::Input => {
+            // Work out where it goes first so a bad address doesn't eat an input
+            writeln!(out, "    let d = {};", d).unwrap();
+            writeln!(out, "    let v = input.get_in()?;").unwrap();
+            writeln!(out, "    if s.write(d, v)? {{").unwrap();
+            writeln!(out, "        return Ok(Exit::Interp({}));", next).unwrap();
+            writeln!(out, "    }}").unwrap();
+        }
+
+        Cmd::Output => writeln!(out, "    output.put_out({})?;", vals[0]).unwrap(),
+
+        Cmd::JumpTrue | Cmd::JumpFalse => {
+            let op = if ins.cmd() == Cmd::JumpTrue {
+                "!="
+            } else {
+                "=="
+            };
+            let jump = if ops[1].0 == Mode::Immediate {
+                match usize_of(ops[1].1) {
+                    Some(t) => format!("return Ok(Exit::Block({}))", t),
+                    None => bail.clone(),
+                }
+            } else {
+                format!(
+                    "return Ok(State::addr({}).map_or(Exit::Interp({}), Exit::Block))",
+                    vals[1], at
+                )
+            };
+
+            // A constant condition either always jumps, ending the block, or never does
+            if ops[0].0 == Mode::Immediate {
+                if (ops[0].1 != 0) == (ins.cmd() == Cmd::JumpTrue) {
+                    writeln!(out, "    {};", jump).unwrap();
+                    return false;
+                }
+            } else {
+                writeln!(out, "    if {} {} 0 {{", vals[0], op).unwrap();
+                writeln!(out, "        {};", jump).unwrap();
+                writeln!(out, "    }}").unwrap();
+            }
+        }
+
+        Cmd::AdjustRel => {
+            writeln!(
+                out,
+                "    s.rel = match s.rel.checked_add({}) {{ Some(r) => r, None => {} }};",
+                vals[0], bail
+            )
+            .unwrap();
+        }
+
+        Cmd::Halt => {
+            writeln!(out, "    Ok(Exit::Halt)").unwrap();
+            return false;
+        }
+
+        // Never decoded from a bare program
+        Cmd::User { .. } => {
+            writeln!(out, "    {};", bail).unwrap();
+            return false;
+        }
+    }
+
+    true
+}
+
+fn usize_of(b: Bit) -> Option<usize> {
+    rt::State::addr(b)
+}
+
+fn block(out: &mut String, b: &Block) {
+    let uses = |cmd| b.code.iter().any(|(_, i, _)| i.cmd() == cmd);
+    let input = if uses(Cmd::Input) { "input" } else { "_input" };
+    let output = if uses(Cmd::Output) {
+        "output"
+    } else {
+        "_output"
+    };
+
+    let mut body = String::new();
+    let mut open = true;
+    for (at, i, ops) in &b.code {
+        let asm = format!(
+            "{} {}",
+            i.cmd().mnemonic(),
+            ops.iter()
+                .map(|o| o.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        writeln!(body, "    // {}: {}", at, asm.trim_end()).unwrap();
+
+        if !ins(&mut body, *at, *i, ops) {
+            open = false;
+            break;
+        }
+    }
+
+    if open {
+        writeln!(body, "    Ok(Exit::Block({}))", b.end()).unwrap();
+    } else if let Some(i) = body.rfind("\n    return ") {
+        // Blocks that end on a jump or a bail finish with the `return` as the tail expression
+        let tail = body[i + 12..].trim_end().trim_end_matches(';').to_owned();
+        body.truncate(i + 5);
+        writeln!(body, "{}", tail).unwrap();
+    }
+
+    // A block of nothing but immediates never looks at the state
+    let state = if body.contains("s.") { "s" } else { "_s" };
+    writeln!(
+        out,
+        "\nfn b{}({}: &mut State, {}: &mut dyn Input, {}: &mut dyn Output) -> Result<Exit> {{\n{}}}",
+        b.start, state, input, output, body
+    )
+    .unwrap();
+}
+
+pub fn transpile(mem: &[Bit]) -> String {
+    let g = graph(mem);
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "// Generated from an intcode program, don't edit by hand\n"
+    )
+    .unwrap();
+    writeln!(out, "use intcode::error::Result;").unwrap();
+    writeln!(out, "use intcode::input::Input;").unwrap();
+    writeln!(out, "use intcode::output::Output;").unwrap();
+    writeln!(out, "use intcode::transpile::rt::{{Exit, State}};").unwrap();
+    writeln!(out, "use intcode::Bit;\n").unwrap();
+
+    writeln!(out, "pub const MEM: &[Bit] = &[").unwrap();
+    for chunk in mem.chunks(MEM_WIDTH) {
+        let vals: Vec<_> = chunk.iter().map(|b| b.to_string()).collect();
+        writeln!(out, "    {},", vals.join(", ")).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+
+    writeln!(out, "const CODE: &[(usize, usize)] = &[").unwrap();
+    for b in g.blocks.values() {
+        writeln!(out, "    ({}, {}),", b.start, b.end()).unwrap();
+    }
+    writeln!(out, "];\n").unwrap();
+
+    writeln!(
+        out,
+        "pub fn run(input: &mut dyn Input, output: &mut dyn Output) -> Result<Vec<Bit>> {{"
+    )
+    .unwrap();
+    writeln!(out, "    let mut s = State::new(MEM, CODE);").unwrap();
+    writeln!(out, "    let mut at = 0;\n").unwrap();
+    writeln!(out, "    loop {{").unwrap();
+    writeln!(out, "        let exit = match at {{").unwrap();
+    for start in g.blocks.keys() {
+        writeln!(
+            out,
+            "            {} => b{}(&mut s, input, output)?,",
+            start, start
+        )
+        .unwrap();
+    }
+    writeln!(out, "            _ => Exit::Interp(at),").unwrap();
+    writeln!(out, "        }};\n").unwrap();
+    writeln!(out, "        match exit {{").unwrap();
+    writeln!(out, "            Exit::Block(next) => at = next,").unwrap();
+    writeln!(
+        out,
+        "            Exit::Interp(idx) => return s.interpret(idx, input, output),"
+    )
+    .unwrap();
+    writeln!(out, "            Exit::Halt => return Ok(s.mem),").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    for b in g.blocks.values() {
+        block(&mut out, b);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rt::State;
+
+    #[test]
+    fn state() {
+        let mut s = State::new(&[1, 2, 3, 4], &[(1, 3)]);
+        assert!(!s.write(0, 5).unwrap());
+        assert!(s.write(2, 5).unwrap());
+        assert!(!s.write(10, 5).unwrap());
+        assert!(s.write(usize::MAX, 5).is_err());
+        assert_eq!(s.read(10), 5);
+
+        s.rel = 2;
+        assert_eq!(s.rel_addr(-2), Some(0));
+        assert_eq!(s.rel_addr(-3), None);
+
+        let mut cin = std::collections::VecDeque::new();
+        let mut cout = vec![];
+        let mem = State::new(&[104, 7, 99], &[])
+            .interpret(0, &mut cin, &mut cout)
+            .unwrap();
+        assert_eq!(mem, vec![104, 7, 99]);
+        assert_eq!(cout, vec![7]);
+    }
+
+    #[test]
+    fn blocks() {
+        let src = transpile(&[3, 9, 1005, 9, 7, 4, 9, 1102, 2, 3, 10, 99]);
+        assert!(src.contains("            0 => b0(&mut s, input, output)?,\n"));
+        assert!(src.contains(
+            "\nfn b7(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {\n\
+             \x20   // 7: multiply #2, #3, [10]\n\
+             \x20   let v = match Bit::checked_mul(2, 3) { Some(v) => v, None => return Ok(Exit::Interp(7)) };\n"
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+//! Watchpoints that fire when an operand reads or an instruction writes a memory address.
+//!
+//! Immediate operands never touch memory so they never trip a watchpoint.
+
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::computer::Cmd;
+use crate::Bit;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Access {
+    fn covers(self, other: Access) -> bool {
+        self == Access::ReadWrite || self == other
+    }
+}
+
+impl Display for Access {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Access::Read => "r",
+            Access::Write => "w",
+            Access::ReadWrite => "rw",
+        })
+    }
+}
+
+impl FromStr for Access {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "r" => Ok(Access::Read),
+            "w" => Ok(Access::Write),
+            "rw" => Ok(Access::ReadWrite),
+            _ => Err(format!("Invalid access {}; use r, w or rw", s)),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Watch<W = Bit> {
+    pub addrs: RangeInclusive<usize>,
+    pub access: Access,
+    /// Only fire when this is the value read or written
+    pub value: Option<W>,
+}
+
+impl<W: PartialEq> Watch<W> {
+    pub fn matches(&self, addr: usize, access: Access, val: &W) -> bool {
+        self.addrs.contains(&addr)
+            && self.access.covers(access)
+            && self.value.as_ref().is_none_or(|v| v == val)
+    }
+}
+
+impl<W: Display> Display for Watch<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.addrs.start() == self.addrs.end() {
+            write!(f, "{} {}", self.addrs.start(), self.access)?;
+        } else {
+            write!(
+                f,
+                "{}..{} {}",
+                self.addrs.start(),
+                self.addrs.end(),
+                self.access
+            )?;
+        }
+
+        match &self.value {
+            Some(v) => write!(f, " ={}", v),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Which instruction tripped a watchpoint and what it did
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct WatchHit<W = Bit> {
+    /// Where the responsible instruction starts
+    pub ins: usize,
+    pub cmd: Cmd,
+    /// The watched address that was touched
+    pub addr: usize,
+    pub access: Access,
+    pub value: W,
+}
+
+impl<W: Display> Display for WatchHit<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let what = match self.access {
+            Access::Write => "written",
+            _ => "read",
+        };
+
+        write!(
+            f,
+            "[{}] {} as {} by {} at {}",
+            self.addr, what, self.value, self.cmd, self.ins
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matching() {
+        let w: Watch = Watch {
+            addrs: 10..=12,
+            access: Access::Write,
+            value: Some(5),
+        };
+
+        assert!(w.matches(11, Access::Write, &5));
+        assert!(!w.matches(11, Access::Write, &4));
+        assert!(!w.matches(11, Access::Read, &5));
+        assert!(!w.matches(13, Access::Write, &5));
+        assert_eq!(w.to_string(), "10..12 w =5");
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+#[cfg(feature = "bigint")]
+use num_traits::{ToPrimitive, Zero};
+
+/// A machine word a [`Computer`](crate::computer::Computer) can run on.
+///
+/// [`Bit`](crate::Bit) is the default everywhere; the other impls trade range for memory.
+pub trait Word: Clone + Eq + Ord + Hash + Debug + Display + FromStr + From<i32> {
+    fn is_zero(&self) -> bool;
+
+    /// The word as an address, if it is a valid one
+    fn to_usize(&self) -> Option<usize>;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    fn wrapping_add(&self, other: &Self) -> Self;
+    fn wrapping_mul(&self, other: &Self) -> Self;
+}
+
+macro_rules! prim_word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            #[inline]
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+
+            #[inline]
+            fn to_usize(&self) -> Option<usize> {
+                use std::convert::TryFrom;
+                usize::try_from(*self).ok()
+            }
+
+            #[inline]
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_add(*self, *other)
+            }
+
+            #[inline]
+            fn checked_mul(&self, other: &Self) -> Option<Self> {
+                <$t>::checked_mul(*self, *other)
+            }
+
+            #[inline]
+            fn wrapping_add(&self, other: &Self) -> Self {
+                <$t>::wrapping_add(*self, *other)
+            }
+
+            #[inline]
+            fn wrapping_mul(&self, other: &Self) -> Self {
+                <$t>::wrapping_mul(*self, *other)
+            }
+        }
+    )*};
+}
+
+prim_word!(i32, i64, i128);
+
+/// Never overflows, so checked and wrapping arithmetic are the same thing
+#[cfg(feature = "bigint")]
+impl Word for BigInt {
+    fn is_zero(&self) -> bool {
+        Zero::is_zero(self)
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        ToPrimitive::to_usize(self)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn wrapping_add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn wrapping_mul(&self, other: &Self) -> Self {
+        self * other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prims() {
+        assert_eq!(Word::checked_add(&i32::MAX, &1), None);
+        assert_eq!(Word::wrapping_add(&i32::MAX, &1), i32::MIN);
+        assert_eq!(Word::to_usize(&-1i64), None);
+        assert_eq!(Word::to_usize(&5i128), Some(5));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big() {
+        let big = BigInt::from(i64::MAX);
+        let sq = Word::checked_mul(&big, &big).unwrap();
+        assert_eq!(
+            sq,
+            BigInt::from(i128::from(i64::MAX) * i128::from(i64::MAX))
+        );
+        assert_eq!(Word::to_usize(&sq), None);
+        assert!(Word::is_zero(&BigInt::from(0)));
+    }
+}
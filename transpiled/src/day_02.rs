@@ -0,0 +1,244 @@
+// Generated from an intcode program, don't edit by hand
+
+use intcode::error::Result;
+use intcode::input::Input;
+use intcode::output::Output;
+use intcode::transpile::rt::{Exit, State};
+use intcode::Bit;
+
+pub const MEM: &[Bit] = &[
+    1, 0, 0, 3, 1, 1, 2, 3, 1, 3, 4, 3, 1, 5, 0, 3,
+    2, 1, 10, 19, 2, 9, 19, 23, 2, 23, 10, 27, 1, 6, 27, 31,
+    1, 31, 6, 35, 2, 35, 10, 39, 1, 39, 5, 43, 2, 6, 43, 47,
+    2, 47, 10, 51, 1, 51, 6, 55, 1, 55, 6, 59, 1, 9, 59, 63,
+    1, 63, 9, 67, 1, 67, 6, 71, 2, 71, 13, 75, 1, 75, 5, 79,
+    1, 79, 9, 83, 2, 6, 83, 87, 1, 87, 5, 91, 2, 6, 91, 95,
+    1, 95, 9, 99, 2, 6, 99, 103, 1, 5, 103, 107, 1, 6, 107, 111,
+    1, 111, 10, 115, 2, 115, 13, 119, 1, 119, 6, 123, 1, 123, 2, 127,
+    1, 127, 5, 0, 99, 2, 14, 0, 0,
+];
+
+const CODE: &[(usize, usize)] = &[
+    (0, 133),
+];
+
+pub fn run(input: &mut dyn Input, output: &mut dyn Output) -> Result<Vec<Bit>> {
+    let mut s = State::new(MEM, CODE);
+    let mut at = 0;
+
+    loop {
+        let exit = match at {
+            0 => b0(&mut s, input, output)?,
+            _ => Exit::Interp(at),
+        };
+
+        match exit {
+            Exit::Block(next) => at = next,
+            Exit::Interp(idx) => return s.interpret(idx, input, output),
+            Exit::Halt => return Ok(s.mem),
+        }
+    }
+}
+
+fn b0(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 0: add [0], [0], [3]
+    let v = match Bit::checked_add(s.read(0), s.read(0)) { Some(v) => v, None => return Ok(Exit::Interp(0)) };
+    let d = 3;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(4));
+    }
+    // 4: add [1], [2], [3]
+    let v = match Bit::checked_add(s.read(1), s.read(2)) { Some(v) => v, None => return Ok(Exit::Interp(4)) };
+    let d = 3;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(8));
+    }
+    // 8: add [3], [4], [3]
+    let v = match Bit::checked_add(s.read(3), s.read(4)) { Some(v) => v, None => return Ok(Exit::Interp(8)) };
+    let d = 3;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(12));
+    }
+    // 12: add [5], [0], [3]
+    let v = match Bit::checked_add(s.read(5), s.read(0)) { Some(v) => v, None => return Ok(Exit::Interp(12)) };
+    let d = 3;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(16));
+    }
+    // 16: multiply [1], [10], [19]
+    let v = match Bit::checked_mul(s.read(1), s.read(10)) { Some(v) => v, None => return Ok(Exit::Interp(16)) };
+    let d = 19;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(20));
+    }
+    // 20: multiply [9], [19], [23]
+    let v = match Bit::checked_mul(s.read(9), s.read(19)) { Some(v) => v, None => return Ok(Exit::Interp(20)) };
+    let d = 23;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(24));
+    }
+    // 24: multiply [23], [10], [27]
+    let v = match Bit::checked_mul(s.read(23), s.read(10)) { Some(v) => v, None => return Ok(Exit::Interp(24)) };
+    let d = 27;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(28));
+    }
+    // 28: add [6], [27], [31]
+    let v = match Bit::checked_add(s.read(6), s.read(27)) { Some(v) => v, None => return Ok(Exit::Interp(28)) };
+    let d = 31;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(32));
+    }
+    // 32: add [31], [6], [35]
+    let v = match Bit::checked_add(s.read(31), s.read(6)) { Some(v) => v, None => return Ok(Exit::Interp(32)) };
+    let d = 35;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(36));
+    }
+    // 36: multiply [35], [10], [39]
+    let v = match Bit::checked_mul(s.read(35), s.read(10)) { Some(v) => v, None => return Ok(Exit::Interp(36)) };
+    let d = 39;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(40));
+    }
+    // 40: add [39], [5], [43]
+    let v = match Bit::checked_add(s.read(39), s.read(5)) { Some(v) => v, None => return Ok(Exit::Interp(40)) };
+    let d = 43;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(44));
+    }
+    // 44: multiply [6], [43], [47]
+    let v = match Bit::checked_mul(s.read(6), s.read(43)) { Some(v) => v, None => return Ok(Exit::Interp(44)) };
+    let d = 47;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(48));
+    }
+    // 48: multiply [47], [10], [51]
+    let v = match Bit::checked_mul(s.read(47), s.read(10)) { Some(v) => v, None => return Ok(Exit::Interp(48)) };
+    let d = 51;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(52));
+    }
+    // 52: add [51], [6], [55]
+    let v = match Bit::checked_add(s.read(51), s.read(6)) { Some(v) => v, None => return Ok(Exit::Interp(52)) };
+    let d = 55;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(56));
+    }
+    // 56: add [55], [6], [59]
+    let v = match Bit::checked_add(s.read(55), s.read(6)) { Some(v) => v, None => return Ok(Exit::Interp(56)) };
+    let d = 59;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(60));
+    }
+    // 60: add [9], [59], [63]
+    let v = match Bit::checked_add(s.read(9), s.read(59)) { Some(v) => v, None => return Ok(Exit::Interp(60)) };
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(64));
+    }
+    // 64: add [63], [9], [67]
+    let v = match Bit::checked_add(s.read(63), s.read(9)) { Some(v) => v, None => return Ok(Exit::Interp(64)) };
+    let d = 67;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(68));
+    }
+    // 68: add [67], [6], [71]
+    let v = match Bit::checked_add(s.read(67), s.read(6)) { Some(v) => v, None => return Ok(Exit::Interp(68)) };
+    let d = 71;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(72));
+    }
+    // 72: multiply [71], [13], [75]
+    let v = match Bit::checked_mul(s.read(71), s.read(13)) { Some(v) => v, None => return Ok(Exit::Interp(72)) };
+    let d = 75;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(76));
+    }
+    // 76: add [75], [5], [79]
+    let v = match Bit::checked_add(s.read(75), s.read(5)) { Some(v) => v, None => return Ok(Exit::Interp(76)) };
+    let d = 79;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(80));
+    }
+    // 80: add [79], [9], [83]
+    let v = match Bit::checked_add(s.read(79), s.read(9)) { Some(v) => v, None => return Ok(Exit::Interp(80)) };
+    let d = 83;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(84));
+    }
+    // 84: multiply [6], [83], [87]
+    let v = match Bit::checked_mul(s.read(6), s.read(83)) { Some(v) => v, None => return Ok(Exit::Interp(84)) };
+    let d = 87;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(88));
+    }
+    // 88: add [87], [5], [91]
+    let v = match Bit::checked_add(s.read(87), s.read(5)) { Some(v) => v, None => return Ok(Exit::Interp(88)) };
+    let d = 91;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(92));
+    }
+    // 92: multiply [6], [91], [95]
+    let v = match Bit::checked_mul(s.read(6), s.read(91)) { Some(v) => v, None => return Ok(Exit::Interp(92)) };
+    let d = 95;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(96));
+    }
+    // 96: add [95], [9], [99]
+    let v = match Bit::checked_add(s.read(95), s.read(9)) { Some(v) => v, None => return Ok(Exit::Interp(96)) };
+    let d = 99;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(100));
+    }
+    // 100: multiply [6], [99], [103]
+    let v = match Bit::checked_mul(s.read(6), s.read(99)) { Some(v) => v, None => return Ok(Exit::Interp(100)) };
+    let d = 103;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(104));
+    }
+    // 104: add [5], [103], [107]
+    let v = match Bit::checked_add(s.read(5), s.read(103)) { Some(v) => v, None => return Ok(Exit::Interp(104)) };
+    let d = 107;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(108));
+    }
+    // 108: add [6], [107], [111]
+    let v = match Bit::checked_add(s.read(6), s.read(107)) { Some(v) => v, None => return Ok(Exit::Interp(108)) };
+    let d = 111;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(112));
+    }
+    // 112: add [111], [10], [115]
+    let v = match Bit::checked_add(s.read(111), s.read(10)) { Some(v) => v, None => return Ok(Exit::Interp(112)) };
+    let d = 115;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(116));
+    }
+    // 116: multiply [115], [13], [119]
+    let v = match Bit::checked_mul(s.read(115), s.read(13)) { Some(v) => v, None => return Ok(Exit::Interp(116)) };
+    let d = 119;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(120));
+    }
+    // 120: add [119], [6], [123]
+    let v = match Bit::checked_add(s.read(119), s.read(6)) { Some(v) => v, None => return Ok(Exit::Interp(120)) };
+    let d = 123;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(124));
+    }
+    // 124: add [123], [2], [127]
+    let v = match Bit::checked_add(s.read(123), s.read(2)) { Some(v) => v, None => return Ok(Exit::Interp(124)) };
+    let d = 127;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(128));
+    }
+    // 128: add [127], [5], [0]
+    let v = match Bit::checked_add(s.read(127), s.read(5)) { Some(v) => v, None => return Ok(Exit::Interp(128)) };
+    let d = 0;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(132));
+    }
+    // 132: halt
+    Ok(Exit::Halt)
+}
@@ -0,0 +1,81 @@
+// Generated from an intcode program, don't edit by hand
+
+use intcode::error::Result;
+use intcode::input::Input;
+use intcode::output::Output;
+use intcode::transpile::rt::{Exit, State};
+use intcode::Bit;
+
+pub const MEM: &[Bit] = &[
+    3, 8, 1001, 8, 10, 8, 105, 1, 0, 0, 21, 38, 55, 64, 81, 106,
+    187, 268, 349, 430, 99999, 3, 9, 101, 2, 9, 9, 1002, 9, 2, 9, 101,
+    5, 9, 9, 4, 9, 99, 3, 9, 102, 2, 9, 9, 101, 3, 9, 9,
+    1002, 9, 4, 9, 4, 9, 99, 3, 9, 102, 2, 9, 9, 4, 9, 99,
+    3, 9, 1002, 9, 5, 9, 1001, 9, 4, 9, 102, 4, 9, 9, 4, 9,
+    99, 3, 9, 102, 2, 9, 9, 1001, 9, 5, 9, 102, 3, 9, 9, 1001,
+    9, 4, 9, 102, 5, 9, 9, 4, 9, 99, 3, 9, 1002, 9, 2, 9,
+    4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9,
+    4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 1001, 9, 2, 9,
+    4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 1001, 9, 1, 9,
+    4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 101, 1, 9, 9,
+    4, 9, 3, 9, 1001, 9, 1, 9, 4, 9, 99, 3, 9, 1002, 9, 2,
+    9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1,
+    9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 101, 2, 9,
+    9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9, 1,
+    9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 102, 2, 9,
+    9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 99, 3, 9, 1002, 9,
+    2, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 102, 2,
+    9, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9,
+    2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9,
+    2, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 1001, 9,
+    2, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 99, 3, 9, 102,
+    2, 9, 9, 4, 9, 3, 9, 1001, 9, 2, 9, 4, 9, 3, 9, 1002,
+    9, 2, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9, 102,
+    2, 9, 9, 4, 9, 3, 9, 101, 2, 9, 9, 4, 9, 3, 9, 101,
+    1, 9, 9, 4, 9, 3, 9, 101, 1, 9, 9, 4, 9, 3, 9, 1001,
+    9, 1, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 99, 3, 9,
+    101, 1, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9,
+    102, 2, 9, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9,
+    101, 1, 9, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 3, 9,
+    1002, 9, 2, 9, 4, 9, 3, 9, 1002, 9, 2, 9, 4, 9, 3, 9,
+    101, 1, 9, 9, 4, 9, 3, 9, 102, 2, 9, 9, 4, 9, 99,
+];
+
+const CODE: &[(usize, usize)] = &[
+    (0, 9),
+];
+
+pub fn run(input: &mut dyn Input, output: &mut dyn Output) -> Result<Vec<Bit>> {
+    let mut s = State::new(MEM, CODE);
+    let mut at = 0;
+
+    loop {
+        let exit = match at {
+            0 => b0(&mut s, input, output)?,
+            _ => Exit::Interp(at),
+        };
+
+        match exit {
+            Exit::Block(next) => at = next,
+            Exit::Interp(idx) => return s.interpret(idx, input, output),
+            Exit::Halt => return Ok(s.mem),
+        }
+    }
+}
+
+fn b0(s: &mut State, input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 0: input [8]
+    let d = 8;
+    let v = input.get_in()?;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(2));
+    }
+    // 2: add [8], #10, [8]
+    let v = match Bit::checked_add(s.read(8), 10) { Some(v) => v, None => return Ok(Exit::Interp(2)) };
+    let d = 8;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(6));
+    }
+    // 6: jumptrue #1, [0]
+    Ok(State::addr(s.read(0)).map_or(Exit::Interp(6), Exit::Block))
+}
@@ -0,0 +1,892 @@
+// Generated from an intcode program, don't edit by hand
+
+use intcode::error::Result;
+use intcode::input::Input;
+use intcode::output::Output;
+use intcode::transpile::rt::{Exit, State};
+use intcode::Bit;
+
+pub const MEM: &[Bit] = &[
+    1102, 34463338, 34463338, 63, 1007, 63, 34463338, 63, 1005, 63, 53, 1102, 3, 1, 1000, 109,
+    988, 209, 12, 9, 1000, 209, 6, 209, 3, 203, 0, 1008, 1000, 1, 63, 1005,
+    63, 65, 1008, 1000, 2, 63, 1005, 63, 904, 1008, 1000, 0, 63, 1005, 63, 58,
+    4, 25, 104, 0, 99, 4, 0, 104, 0, 99, 4, 17, 104, 0, 99, 0,
+    0, 1101, 0, 33, 1017, 1101, 24, 0, 1014, 1101, 519, 0, 1028, 1102, 34, 1,
+    1004, 1101, 0, 31, 1007, 1101, 0, 844, 1025, 1102, 0, 1, 1020, 1102, 38, 1,
+    1003, 1102, 39, 1, 1008, 1102, 849, 1, 1024, 1101, 0, 22, 1001, 1102, 25, 1,
+    1009, 1101, 1, 0, 1021, 1101, 0, 407, 1022, 1101, 404, 0, 1023, 1101, 0, 35,
+    1013, 1101, 27, 0, 1011, 1101, 0, 37, 1016, 1102, 1, 26, 1019, 1102, 28, 1,
+    1015, 1101, 0, 30, 1000, 1102, 1, 36, 1005, 1101, 0, 29, 1002, 1101, 23, 0,
+    1012, 1102, 1, 32, 1010, 1102, 21, 1, 1006, 1101, 808, 0, 1027, 1102, 20, 1,
+    1018, 1101, 0, 514, 1029, 1102, 1, 815, 1026, 109, 14, 2107, 24, -5, 63, 1005,
+    63, 199, 4, 187, 1105, 1, 203, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+    -1, 2108, 21, -7, 63, 1005, 63, 225, 4, 209, 1001, 64, 1, 64, 1106, 0,
+    225, 1002, 64, 2, 64, 109, -16, 1201, 6, 0, 63, 1008, 63, 35, 63, 1005,
+    63, 249, 1001, 64, 1, 64, 1106, 0, 251, 4, 231, 1002, 64, 2, 64, 109,
+    9, 2102, 1, 2, 63, 1008, 63, 37, 63, 1005, 63, 271, 1105, 1, 277, 4,
+    257, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 11, 1208, -8, 23, 63, 1005,
+    63, 293, 1105, 1, 299, 4, 283, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+    8, 21107, 40, 39, -8, 1005, 1017, 319, 1001, 64, 1, 64, 1106, 0, 321, 4,
+    305, 1002, 64, 2, 64, 109, -28, 2101, 0, 6, 63, 1008, 63, 39, 63, 1005,
+    63, 341, 1106, 0, 347, 4, 327, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+    19, 2107, 26, -7, 63, 1005, 63, 363, 1106, 0, 369, 4, 353, 1001, 64, 1,
+    64, 1002, 64, 2, 64, 109, 1, 1202, -9, 1, 63, 1008, 63, 39, 63, 1005,
+    63, 395, 4, 375, 1001, 64, 1, 64, 1105, 1, 395, 1002, 64, 2, 64, 109,
+    9, 2105, 1, -3, 1106, 0, 413, 4, 401, 1001, 64, 1, 64, 1002, 64, 2,
+    64, 109, -13, 1207, -4, 26, 63, 1005, 63, 435, 4, 419, 1001, 64, 1, 64,
+    1105, 1, 435, 1002, 64, 2, 64, 109, -1, 21101, 41, 0, 7, 1008, 1019, 41,
+    63, 1005, 63, 461, 4, 441, 1001, 64, 1, 64, 1105, 1, 461, 1002, 64, 2,
+    64, 109, 7, 21107, 42, 43, -2, 1005, 1017, 479, 4, 467, 1105, 1, 483, 1001,
+    64, 1, 64, 1002, 64, 2, 64, 109, -6, 21108, 43, 46, 0, 1005, 1013, 499,
+    1106, 0, 505, 4, 489, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 17, 2106,
+    0, -2, 4, 511, 1105, 1, 523, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+    -27, 1202, -1, 1, 63, 1008, 63, 28, 63, 1005, 63, 547, 1001, 64, 1, 64,
+    1106, 0, 549, 4, 529, 1002, 64, 2, 64, 109, 18, 1206, -1, 567, 4, 555,
+    1001, 64, 1, 64, 1106, 0, 567, 1002, 64, 2, 64, 109, -16, 21102, 44, 1,
+    6, 1008, 1011, 43, 63, 1005, 63, 587, 1106, 0, 593, 4, 573, 1001, 64, 1,
+    64, 1002, 64, 2, 64, 109, 8, 21102, 45, 1, -1, 1008, 1012, 45, 63, 1005,
+    63, 619, 4, 599, 1001, 64, 1, 64, 1105, 1, 619, 1002, 64, 2, 64, 109,
+    7, 1205, 1, 633, 4, 625, 1106, 0, 637, 1001, 64, 1, 64, 1002, 64, 2,
+    64, 109, -8, 2102, 1, -3, 63, 1008, 63, 25, 63, 1005, 63, 659, 4, 643,
+    1105, 1, 663, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 14, 1206, -5, 679,
+    1001, 64, 1, 64, 1105, 1, 681, 4, 669, 1002, 64, 2, 64, 109, -28, 2101,
+    0, 2, 63, 1008, 63, 30, 63, 1005, 63, 707, 4, 687, 1001, 64, 1, 64,
+    1106, 0, 707, 1002, 64, 2, 64, 109, 21, 21101, 46, 0, 0, 1008, 1019, 48,
+    63, 1005, 63, 727, 1106, 0, 733, 4, 713, 1001, 64, 1, 64, 1002, 64, 2,
+    64, 109, -3, 21108, 47, 47, 1, 1005, 1017, 751, 4, 739, 1106, 0, 755, 1001,
+    64, 1, 64, 1002, 64, 2, 64, 109, -13, 1207, 0, 37, 63, 1005, 63, 771,
+    1105, 1, 777, 4, 761, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 7, 2108,
+    21, -9, 63, 1005, 63, 797, 1001, 64, 1, 64, 1105, 1, 799, 4, 783, 1002,
+    64, 2, 64, 109, 22, 2106, 0, -5, 1001, 64, 1, 64, 1106, 0, 817, 4,
+    805, 1002, 64, 2, 64, 109, -4, 1205, -8, 829, 1106, 0, 835, 4, 823, 1001,
+    64, 1, 64, 1002, 64, 2, 64, 109, -4, 2105, 1, 0, 4, 841, 1105, 1,
+    853, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -30, 1208, 6, 30, 63, 1005,
+    63, 871, 4, 859, 1105, 1, 875, 1001, 64, 1, 64, 1002, 64, 2, 64, 109,
+    -2, 1201, 9, 0, 63, 1008, 63, 22, 63, 1005, 63, 897, 4, 881, 1106, 0,
+    901, 1001, 64, 1, 64, 4, 64, 99, 21101, 27, 0, 1, 21102, 1, 915, 0,
+    1106, 0, 922, 21201, 1, 66266, 1, 204, 1, 99, 109, 3, 1207, -2, 3, 63,
+    1005, 63, 964, 21201, -2, -1, 1, 21102, 942, 1, 0, 1105, 1, 922, 22101, 0,
+    1, -1, 21201, -2, -3, 1, 21101, 0, 957, 0, 1106, 0, 922, 22201, 1, -1,
+    -2, 1105, 1, 968, 21202, -2, 1, -2, 109, -3, 2106, 0, 0,
+];
+
+const CODE: &[(usize, usize)] = &[
+    (0, 11),
+    (11, 34),
+    (34, 41),
+    (41, 48),
+    (48, 53),
+    (53, 58),
+    (58, 63),
+    (65, 194),
+    (194, 199),
+    (199, 203),
+    (203, 216),
+    (216, 225),
+    (225, 242),
+    (242, 249),
+    (249, 251),
+    (251, 268),
+    (268, 271),
+    (271, 277),
+    (277, 290),
+    (290, 293),
+    (293, 299),
+    (299, 312),
+    (312, 319),
+    (319, 321),
+    (321, 338),
+    (338, 341),
+    (341, 347),
+    (347, 360),
+    (360, 363),
+    (363, 369),
+    (369, 386),
+    (386, 395),
+    (395, 404),
+    (904, 915),
+    (922, 931),
+    (931, 942),
+    (964, 973),
+];
+
+pub fn run(input: &mut dyn Input, output: &mut dyn Output) -> Result<Vec<Bit>> {
+    let mut s = State::new(MEM, CODE);
+    let mut at = 0;
+
+    loop {
+        let exit = match at {
+            0 => b0(&mut s, input, output)?,
+            11 => b11(&mut s, input, output)?,
+            34 => b34(&mut s, input, output)?,
+            41 => b41(&mut s, input, output)?,
+            48 => b48(&mut s, input, output)?,
+            53 => b53(&mut s, input, output)?,
+            58 => b58(&mut s, input, output)?,
+            65 => b65(&mut s, input, output)?,
+            194 => b194(&mut s, input, output)?,
+            199 => b199(&mut s, input, output)?,
+            203 => b203(&mut s, input, output)?,
+            216 => b216(&mut s, input, output)?,
+            225 => b225(&mut s, input, output)?,
+            242 => b242(&mut s, input, output)?,
+            249 => b249(&mut s, input, output)?,
+            251 => b251(&mut s, input, output)?,
+            268 => b268(&mut s, input, output)?,
+            271 => b271(&mut s, input, output)?,
+            277 => b277(&mut s, input, output)?,
+            290 => b290(&mut s, input, output)?,
+            293 => b293(&mut s, input, output)?,
+            299 => b299(&mut s, input, output)?,
+            312 => b312(&mut s, input, output)?,
+            319 => b319(&mut s, input, output)?,
+            321 => b321(&mut s, input, output)?,
+            338 => b338(&mut s, input, output)?,
+            341 => b341(&mut s, input, output)?,
+            347 => b347(&mut s, input, output)?,
+            360 => b360(&mut s, input, output)?,
+            363 => b363(&mut s, input, output)?,
+            369 => b369(&mut s, input, output)?,
+            386 => b386(&mut s, input, output)?,
+            395 => b395(&mut s, input, output)?,
+            904 => b904(&mut s, input, output)?,
+            922 => b922(&mut s, input, output)?,
+            931 => b931(&mut s, input, output)?,
+            964 => b964(&mut s, input, output)?,
+            _ => Exit::Interp(at),
+        };
+
+        match exit {
+            Exit::Block(next) => at = next,
+            Exit::Interp(idx) => return s.interpret(idx, input, output),
+            Exit::Halt => return Ok(s.mem),
+        }
+    }
+}
+
+fn b0(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 0: multiply #34463338, #34463338, [63]
+    let v = match Bit::checked_mul(34463338, 34463338) { Some(v) => v, None => return Ok(Exit::Interp(0)) };
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(4));
+    }
+    // 4: lessthan [63], #34463338, [63]
+    let v = (s.read(63) < 34463338) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(8));
+    }
+    // 8: jumptrue [63], #53
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(53));
+    }
+    Ok(Exit::Block(11))
+}
+
+fn b11(s: &mut State, input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 11: multiply #3, #1, [1000]
+    let v = match Bit::checked_mul(3, 1) { Some(v) => v, None => return Ok(Exit::Interp(11)) };
+    let d = 1000;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(15));
+    }
+    // 15: adjustrel #988
+    s.rel = match s.rel.checked_add(988) { Some(r) => r, None => return Ok(Exit::Interp(15)) };
+    // 17: adjustrel rel+12
+    s.rel = match s.rel.checked_add(s.read(match s.rel_addr(12) { Some(a) => a, None => return Ok(Exit::Interp(17)) })) { Some(r) => r, None => return Ok(Exit::Interp(17)) };
+    // 19: adjustrel [1000]
+    s.rel = match s.rel.checked_add(s.read(1000)) { Some(r) => r, None => return Ok(Exit::Interp(19)) };
+    // 21: adjustrel rel+6
+    s.rel = match s.rel.checked_add(s.read(match s.rel_addr(6) { Some(a) => a, None => return Ok(Exit::Interp(21)) })) { Some(r) => r, None => return Ok(Exit::Interp(21)) };
+    // 23: adjustrel rel+3
+    s.rel = match s.rel.checked_add(s.read(match s.rel_addr(3) { Some(a) => a, None => return Ok(Exit::Interp(23)) })) { Some(r) => r, None => return Ok(Exit::Interp(23)) };
+    // 25: input rel+0
+    let d = match s.rel_addr(0) { Some(a) => a, None => return Ok(Exit::Interp(25)) };
+    let v = input.get_in()?;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(27));
+    }
+    // 27: equals [1000], #1, [63]
+    let v = (s.read(1000) == 1) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(31));
+    }
+    // 31: jumptrue [63], #65
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(65));
+    }
+    Ok(Exit::Block(34))
+}
+
+fn b34(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 34: equals [1000], #2, [63]
+    let v = (s.read(1000) == 2) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(38));
+    }
+    // 38: jumptrue [63], #904
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(904));
+    }
+    Ok(Exit::Block(41))
+}
+
+fn b41(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 41: equals [1000], #0, [63]
+    let v = (s.read(1000) == 0) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(45));
+    }
+    // 45: jumptrue [63], #58
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(58));
+    }
+    Ok(Exit::Block(48))
+}
+
+fn b48(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 48: output [25]
+    output.put_out(s.read(25))?;
+    // 50: output #0
+    output.put_out(0)?;
+    // 52: halt
+    Ok(Exit::Halt)
+}
+
+fn b53(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 53: output [0]
+    output.put_out(s.read(0))?;
+    // 55: output #0
+    output.put_out(0)?;
+    // 57: halt
+    Ok(Exit::Halt)
+}
+
+fn b58(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 58: output [17]
+    output.put_out(s.read(17))?;
+    // 60: output #0
+    output.put_out(0)?;
+    // 62: halt
+    Ok(Exit::Halt)
+}
+
+fn b65(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 65: add #0, #33, [1017]
+    let v = match Bit::checked_add(0, 33) { Some(v) => v, None => return Ok(Exit::Interp(65)) };
+    let d = 1017;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(69));
+    }
+    // 69: add #24, #0, [1014]
+    let v = match Bit::checked_add(24, 0) { Some(v) => v, None => return Ok(Exit::Interp(69)) };
+    let d = 1014;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(73));
+    }
+    // 73: add #519, #0, [1028]
+    let v = match Bit::checked_add(519, 0) { Some(v) => v, None => return Ok(Exit::Interp(73)) };
+    let d = 1028;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(77));
+    }
+    // 77: multiply #34, #1, [1004]
+    let v = match Bit::checked_mul(34, 1) { Some(v) => v, None => return Ok(Exit::Interp(77)) };
+    let d = 1004;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(81));
+    }
+    // 81: add #0, #31, [1007]
+    let v = match Bit::checked_add(0, 31) { Some(v) => v, None => return Ok(Exit::Interp(81)) };
+    let d = 1007;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(85));
+    }
+    // 85: add #0, #844, [1025]
+    let v = match Bit::checked_add(0, 844) { Some(v) => v, None => return Ok(Exit::Interp(85)) };
+    let d = 1025;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(89));
+    }
+    // 89: multiply #0, #1, [1020]
+    let v = match Bit::checked_mul(0, 1) { Some(v) => v, None => return Ok(Exit::Interp(89)) };
+    let d = 1020;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(93));
+    }
+    // 93: multiply #38, #1, [1003]
+    let v = match Bit::checked_mul(38, 1) { Some(v) => v, None => return Ok(Exit::Interp(93)) };
+    let d = 1003;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(97));
+    }
+    // 97: multiply #39, #1, [1008]
+    let v = match Bit::checked_mul(39, 1) { Some(v) => v, None => return Ok(Exit::Interp(97)) };
+    let d = 1008;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(101));
+    }
+    // 101: multiply #849, #1, [1024]
+    let v = match Bit::checked_mul(849, 1) { Some(v) => v, None => return Ok(Exit::Interp(101)) };
+    let d = 1024;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(105));
+    }
+    // 105: add #0, #22, [1001]
+    let v = match Bit::checked_add(0, 22) { Some(v) => v, None => return Ok(Exit::Interp(105)) };
+    let d = 1001;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(109));
+    }
+    // 109: multiply #25, #1, [1009]
+    let v = match Bit::checked_mul(25, 1) { Some(v) => v, None => return Ok(Exit::Interp(109)) };
+    let d = 1009;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(113));
+    }
+    // 113: add #1, #0, [1021]
+    let v = match Bit::checked_add(1, 0) { Some(v) => v, None => return Ok(Exit::Interp(113)) };
+    let d = 1021;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(117));
+    }
+    // 117: add #0, #407, [1022]
+    let v = match Bit::checked_add(0, 407) { Some(v) => v, None => return Ok(Exit::Interp(117)) };
+    let d = 1022;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(121));
+    }
+    // 121: add #404, #0, [1023]
+    let v = match Bit::checked_add(404, 0) { Some(v) => v, None => return Ok(Exit::Interp(121)) };
+    let d = 1023;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(125));
+    }
+    // 125: add #0, #35, [1013]
+    let v = match Bit::checked_add(0, 35) { Some(v) => v, None => return Ok(Exit::Interp(125)) };
+    let d = 1013;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(129));
+    }
+    // 129: add #27, #0, [1011]
+    let v = match Bit::checked_add(27, 0) { Some(v) => v, None => return Ok(Exit::Interp(129)) };
+    let d = 1011;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(133));
+    }
+    // 133: add #0, #37, [1016]
+    let v = match Bit::checked_add(0, 37) { Some(v) => v, None => return Ok(Exit::Interp(133)) };
+    let d = 1016;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(137));
+    }
+    // 137: multiply #1, #26, [1019]
+    let v = match Bit::checked_mul(1, 26) { Some(v) => v, None => return Ok(Exit::Interp(137)) };
+    let d = 1019;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(141));
+    }
+    // 141: multiply #28, #1, [1015]
+    let v = match Bit::checked_mul(28, 1) { Some(v) => v, None => return Ok(Exit::Interp(141)) };
+    let d = 1015;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(145));
+    }
+    // 145: add #0, #30, [1000]
+    let v = match Bit::checked_add(0, 30) { Some(v) => v, None => return Ok(Exit::Interp(145)) };
+    let d = 1000;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(149));
+    }
+    // 149: multiply #1, #36, [1005]
+    let v = match Bit::checked_mul(1, 36) { Some(v) => v, None => return Ok(Exit::Interp(149)) };
+    let d = 1005;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(153));
+    }
+    // 153: add #0, #29, [1002]
+    let v = match Bit::checked_add(0, 29) { Some(v) => v, None => return Ok(Exit::Interp(153)) };
+    let d = 1002;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(157));
+    }
+    // 157: add #23, #0, [1012]
+    let v = match Bit::checked_add(23, 0) { Some(v) => v, None => return Ok(Exit::Interp(157)) };
+    let d = 1012;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(161));
+    }
+    // 161: multiply #1, #32, [1010]
+    let v = match Bit::checked_mul(1, 32) { Some(v) => v, None => return Ok(Exit::Interp(161)) };
+    let d = 1010;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(165));
+    }
+    // 165: multiply #21, #1, [1006]
+    let v = match Bit::checked_mul(21, 1) { Some(v) => v, None => return Ok(Exit::Interp(165)) };
+    let d = 1006;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(169));
+    }
+    // 169: add #808, #0, [1027]
+    let v = match Bit::checked_add(808, 0) { Some(v) => v, None => return Ok(Exit::Interp(169)) };
+    let d = 1027;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(173));
+    }
+    // 173: multiply #20, #1, [1018]
+    let v = match Bit::checked_mul(20, 1) { Some(v) => v, None => return Ok(Exit::Interp(173)) };
+    let d = 1018;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(177));
+    }
+    // 177: add #0, #514, [1029]
+    let v = match Bit::checked_add(0, 514) { Some(v) => v, None => return Ok(Exit::Interp(177)) };
+    let d = 1029;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(181));
+    }
+    // 181: multiply #1, #815, [1026]
+    let v = match Bit::checked_mul(1, 815) { Some(v) => v, None => return Ok(Exit::Interp(181)) };
+    let d = 1026;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(185));
+    }
+    // 185: adjustrel #14
+    s.rel = match s.rel.checked_add(14) { Some(r) => r, None => return Ok(Exit::Interp(185)) };
+    // 187: lessthan #24, rel-5, [63]
+    let v = (24 < s.read(match s.rel_addr(-5) { Some(a) => a, None => return Ok(Exit::Interp(187)) })) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(191));
+    }
+    // 191: jumptrue [63], #199
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(199));
+    }
+    Ok(Exit::Block(194))
+}
+
+fn b194(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 194: output [187]
+    output.put_out(s.read(187))?;
+    // 196: jumptrue #1, #203
+    Ok(Exit::Block(203))
+}
+
+fn b199(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 199: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(199)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(203));
+    }
+    Ok(Exit::Block(203))
+}
+
+fn b203(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 203: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(203)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(207));
+    }
+    // 207: adjustrel #-1
+    s.rel = match s.rel.checked_add(-1) { Some(r) => r, None => return Ok(Exit::Interp(207)) };
+    // 209: equals #21, rel-7, [63]
+    let v = (21 == s.read(match s.rel_addr(-7) { Some(a) => a, None => return Ok(Exit::Interp(209)) })) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(213));
+    }
+    // 213: jumptrue [63], #225
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(225));
+    }
+    Ok(Exit::Block(216))
+}
+
+fn b216(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 216: output [209]
+    output.put_out(s.read(209))?;
+    // 218: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(218)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(222));
+    }
+    // 222: jumpfalse #0, #225
+    Ok(Exit::Block(225))
+}
+
+fn b225(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 225: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(225)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(229));
+    }
+    // 229: adjustrel #-16
+    s.rel = match s.rel.checked_add(-16) { Some(r) => r, None => return Ok(Exit::Interp(229)) };
+    // 231: add rel+6, #0, [63]
+    let v = match Bit::checked_add(s.read(match s.rel_addr(6) { Some(a) => a, None => return Ok(Exit::Interp(231)) }), 0) { Some(v) => v, None => return Ok(Exit::Interp(231)) };
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(235));
+    }
+    // 235: equals [63], #35, [63]
+    let v = (s.read(63) == 35) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(239));
+    }
+    // 239: jumptrue [63], #249
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(249));
+    }
+    Ok(Exit::Block(242))
+}
+
+fn b242(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 242: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(242)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(246));
+    }
+    // 246: jumpfalse #0, #251
+    Ok(Exit::Block(251))
+}
+
+fn b249(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 249: output [231]
+    output.put_out(s.read(231))?;
+    Ok(Exit::Block(251))
+}
+
+fn b251(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 251: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(251)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(255));
+    }
+    // 255: adjustrel #9
+    s.rel = match s.rel.checked_add(9) { Some(r) => r, None => return Ok(Exit::Interp(255)) };
+    // 257: multiply #1, rel+2, [63]
+    let v = match Bit::checked_mul(1, s.read(match s.rel_addr(2) { Some(a) => a, None => return Ok(Exit::Interp(257)) })) { Some(v) => v, None => return Ok(Exit::Interp(257)) };
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(261));
+    }
+    // 261: equals [63], #37, [63]
+    let v = (s.read(63) == 37) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(265));
+    }
+    // 265: jumptrue [63], #271
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(271));
+    }
+    Ok(Exit::Block(268))
+}
+
+fn b268(_s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 268: jumptrue #1, #277
+    Ok(Exit::Block(277))
+}
+
+fn b271(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 271: output [257]
+    output.put_out(s.read(257))?;
+    // 273: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(273)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(277));
+    }
+    Ok(Exit::Block(277))
+}
+
+fn b277(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 277: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(277)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(281));
+    }
+    // 281: adjustrel #11
+    s.rel = match s.rel.checked_add(11) { Some(r) => r, None => return Ok(Exit::Interp(281)) };
+    // 283: equals rel-8, #23, [63]
+    let v = (s.read(match s.rel_addr(-8) { Some(a) => a, None => return Ok(Exit::Interp(283)) }) == 23) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(287));
+    }
+    // 287: jumptrue [63], #293
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(293));
+    }
+    Ok(Exit::Block(290))
+}
+
+fn b290(_s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 290: jumptrue #1, #299
+    Ok(Exit::Block(299))
+}
+
+fn b293(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 293: output [283]
+    output.put_out(s.read(283))?;
+    // 295: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(295)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(299));
+    }
+    Ok(Exit::Block(299))
+}
+
+fn b299(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 299: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(299)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(303));
+    }
+    // 303: adjustrel #8
+    s.rel = match s.rel.checked_add(8) { Some(r) => r, None => return Ok(Exit::Interp(303)) };
+    // 305: lessthan #40, #39, rel-8
+    let d = match s.rel_addr(-8) { Some(a) => a, None => return Ok(Exit::Interp(305)) };
+    if s.write(d, 0)? {
+        return Ok(Exit::Interp(309));
+    }
+    // 309: jumptrue [1017], #319
+    if s.read(1017) != 0 {
+        return Ok(Exit::Block(319));
+    }
+    Ok(Exit::Block(312))
+}
+
+fn b312(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 312: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(312)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(316));
+    }
+    // 316: jumpfalse #0, #321
+    Ok(Exit::Block(321))
+}
+
+fn b319(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 319: output [305]
+    output.put_out(s.read(305))?;
+    Ok(Exit::Block(321))
+}
+
+fn b321(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 321: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(321)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(325));
+    }
+    // 325: adjustrel #-28
+    s.rel = match s.rel.checked_add(-28) { Some(r) => r, None => return Ok(Exit::Interp(325)) };
+    // 327: add #0, rel+6, [63]
+    let v = match Bit::checked_add(0, s.read(match s.rel_addr(6) { Some(a) => a, None => return Ok(Exit::Interp(327)) })) { Some(v) => v, None => return Ok(Exit::Interp(327)) };
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(331));
+    }
+    // 331: equals [63], #39, [63]
+    let v = (s.read(63) == 39) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(335));
+    }
+    // 335: jumptrue [63], #341
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(341));
+    }
+    Ok(Exit::Block(338))
+}
+
+fn b338(_s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 338: jumpfalse #0, #347
+    Ok(Exit::Block(347))
+}
+
+fn b341(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 341: output [327]
+    output.put_out(s.read(327))?;
+    // 343: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(343)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(347));
+    }
+    Ok(Exit::Block(347))
+}
+
+fn b347(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 347: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(347)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(351));
+    }
+    // 351: adjustrel #19
+    s.rel = match s.rel.checked_add(19) { Some(r) => r, None => return Ok(Exit::Interp(351)) };
+    // 353: lessthan #26, rel-7, [63]
+    let v = (26 < s.read(match s.rel_addr(-7) { Some(a) => a, None => return Ok(Exit::Interp(353)) })) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(357));
+    }
+    // 357: jumptrue [63], #363
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(363));
+    }
+    Ok(Exit::Block(360))
+}
+
+fn b360(_s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 360: jumpfalse #0, #369
+    Ok(Exit::Block(369))
+}
+
+fn b363(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 363: output [353]
+    output.put_out(s.read(353))?;
+    // 365: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(365)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(369));
+    }
+    Ok(Exit::Block(369))
+}
+
+fn b369(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 369: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(369)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(373));
+    }
+    // 373: adjustrel #1
+    s.rel = match s.rel.checked_add(1) { Some(r) => r, None => return Ok(Exit::Interp(373)) };
+    // 375: multiply rel-9, #1, [63]
+    let v = match Bit::checked_mul(s.read(match s.rel_addr(-9) { Some(a) => a, None => return Ok(Exit::Interp(375)) }), 1) { Some(v) => v, None => return Ok(Exit::Interp(375)) };
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(379));
+    }
+    // 379: equals [63], #39, [63]
+    let v = (s.read(63) == 39) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(383));
+    }
+    // 383: jumptrue [63], #395
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(395));
+    }
+    Ok(Exit::Block(386))
+}
+
+fn b386(s: &mut State, _input: &mut dyn Input, output: &mut dyn Output) -> Result<Exit> {
+    // 386: output [375]
+    output.put_out(s.read(375))?;
+    // 388: add [64], #1, [64]
+    let v = match Bit::checked_add(s.read(64), 1) { Some(v) => v, None => return Ok(Exit::Interp(388)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(392));
+    }
+    // 392: jumptrue #1, #395
+    Ok(Exit::Block(395))
+}
+
+fn b395(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 395: multiply [64], #2, [64]
+    let v = match Bit::checked_mul(s.read(64), 2) { Some(v) => v, None => return Ok(Exit::Interp(395)) };
+    let d = 64;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(399));
+    }
+    // 399: adjustrel #9
+    s.rel = match s.rel.checked_add(9) { Some(r) => r, None => return Ok(Exit::Interp(399)) };
+    // 401: jumptrue #1, rel-3
+    Ok(State::addr(s.read(match s.rel_addr(-3) { Some(a) => a, None => return Ok(Exit::Interp(401)) })).map_or(Exit::Interp(401), Exit::Block))
+}
+
+fn b904(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 904: add #27, #0, rel+1
+    let v = match Bit::checked_add(27, 0) { Some(v) => v, None => return Ok(Exit::Interp(904)) };
+    let d = match s.rel_addr(1) { Some(a) => a, None => return Ok(Exit::Interp(904)) };
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(908));
+    }
+    // 908: multiply #1, #915, rel+0
+    let v = match Bit::checked_mul(1, 915) { Some(v) => v, None => return Ok(Exit::Interp(908)) };
+    let d = match s.rel_addr(0) { Some(a) => a, None => return Ok(Exit::Interp(908)) };
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(912));
+    }
+    // 912: jumpfalse #0, #922
+    Ok(Exit::Block(922))
+}
+
+fn b922(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 922: adjustrel #3
+    s.rel = match s.rel.checked_add(3) { Some(r) => r, None => return Ok(Exit::Interp(922)) };
+    // 924: lessthan rel-2, #3, [63]
+    let v = (s.read(match s.rel_addr(-2) { Some(a) => a, None => return Ok(Exit::Interp(924)) }) < 3) as Bit;
+    let d = 63;
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(928));
+    }
+    // 928: jumptrue [63], #964
+    if s.read(63) != 0 {
+        return Ok(Exit::Block(964));
+    }
+    Ok(Exit::Block(931))
+}
+
+fn b931(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 931: add rel-2, #-1, rel+1
+    let v = match Bit::checked_add(s.read(match s.rel_addr(-2) { Some(a) => a, None => return Ok(Exit::Interp(931)) }), -1) { Some(v) => v, None => return Ok(Exit::Interp(931)) };
+    let d = match s.rel_addr(1) { Some(a) => a, None => return Ok(Exit::Interp(931)) };
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(935));
+    }
+    // 935: multiply #942, #1, rel+0
+    let v = match Bit::checked_mul(942, 1) { Some(v) => v, None => return Ok(Exit::Interp(935)) };
+    let d = match s.rel_addr(0) { Some(a) => a, None => return Ok(Exit::Interp(935)) };
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(939));
+    }
+    // 939: jumptrue #1, #922
+    Ok(Exit::Block(922))
+}
+
+fn b964(s: &mut State, _input: &mut dyn Input, _output: &mut dyn Output) -> Result<Exit> {
+    // 964: multiply rel-2, #1, rel-2
+    let v = match Bit::checked_mul(s.read(match s.rel_addr(-2) { Some(a) => a, None => return Ok(Exit::Interp(964)) }), 1) { Some(v) => v, None => return Ok(Exit::Interp(964)) };
+    let d = match s.rel_addr(-2) { Some(a) => a, None => return Ok(Exit::Interp(964)) };
+    if s.write(d, v)? {
+        return Ok(Exit::Interp(968));
+    }
+    // 968: adjustrel #-3
+    s.rel = match s.rel.checked_add(-3) { Some(r) => r, None => return Ok(Exit::Interp(968)) };
+    // 970: jumpfalse #0, rel+0
+    Ok(State::addr(s.read(match s.rel_addr(0) { Some(a) => a, None => return Ok(Exit::Interp(970)) })).map_or(Exit::Interp(970), Exit::Block))
+}
@@ -0,0 +1,93 @@
+//! Every checked-in intcode program compiled to Rust by `intcode::transpile`.
+//!
+//! Each day gets a module with its `MEM` and a `run` that behaves exactly like running `MEM` on
+//! a `Computer`.  The modules are checked in rather than built by a build script, so intcode
+//! isn't compiled twice; after changing the transpiler or an input, regenerate them with
+//!
+//! ```text
+//! cargo run -p intcode --bin transpile day_02/input > transpiled/src/day_02.rs
+//! ```
+
+#[rustfmt::skip]
+pub mod day_02;
+#[rustfmt::skip]
+pub mod day_05;
+#[rustfmt::skip]
+pub mod day_07;
+#[rustfmt::skip]
+pub mod day_09;
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use intcode::computer::Computer;
+    use intcode::error::Result;
+    use intcode::input::Input;
+    use intcode::output::Output;
+    use intcode::transpile::transpile;
+    use intcode::Bit;
+
+    type Run = fn(&mut dyn Input, &mut dyn Output) -> Result<Vec<Bit>>;
+
+    /// The final memory (or error) and the outputs
+    type Outcome = (std::result::Result<Vec<Bit>, String>, Vec<Bit>);
+
+    fn interpreted(mem: &[Bit], input: &[Bit]) -> Outcome {
+        let mut cin: VecDeque<Bit> = input.iter().copied().collect();
+        let mut cout = vec![];
+
+        let mut c = Computer::new(mem.to_vec(), &mut cin, &mut cout);
+        let res = c.run().map(|_| c.mem.clone()).map_err(|e| e.to_string());
+
+        drop(c);
+        (res, cout)
+    }
+
+    fn transpiled(run: Run, input: &[Bit]) -> Outcome {
+        let mut cin: VecDeque<Bit> = input.iter().copied().collect();
+        let mut cout = vec![];
+
+        let res = run(&mut cin, &mut cout).map_err(|e| e.to_string());
+        (res, cout)
+    }
+
+    fn check(run: Run, mem: &[Bit], inputs: &[&[Bit]]) {
+        for input in inputs {
+            assert_eq!(
+                transpiled(run, input),
+                interpreted(mem, input),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn up_to_date() {
+        let days = [
+            ("day_02", include_str!("day_02.rs")),
+            ("day_05", include_str!("day_05.rs")),
+            ("day_07", include_str!("day_07.rs")),
+            ("day_09", include_str!("day_09.rs")),
+        ];
+
+        for (day, src) in &days {
+            let path = format!("{}/../{}/input", env!("CARGO_MANIFEST_DIR"), day);
+            let mem = Computer::get_bits(path).unwrap().remove(0);
+            assert!(transpile(&mem) == *src, "{} needs regenerating", day);
+        }
+    }
+
+    #[test]
+    fn same_as_interpreter() {
+        check(super::day_02::run, super::day_02::MEM, &[&[]]);
+        check(super::day_05::run, super::day_05::MEM, &[&[1], &[5], &[8]]);
+        check(
+            super::day_07::run,
+            super::day_07::MEM,
+            &[&[0, 0], &[1, 5], &[2, 17], &[3, 1], &[4, 99]],
+        );
+        check(super::day_09::run, super::day_09::MEM, &[&[1], &[2]]);
+    }
+}